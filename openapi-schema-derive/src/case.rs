@@ -0,0 +1,98 @@
+//! Case conversion rules of `#[serde(rename_all = "...")]`.
+//!
+//! The conversions mirror the ones implemented in `serde_derive`, so that the
+//! generated property names and enum values match the serialized output.
+
+use self::RenameRule::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RenameRule {
+    /// Don't apply a default rename rule.
+    None,
+    /// Rename to "lowercase" style.
+    LowerCase,
+    /// Rename to "UPPERCASE" style.
+    UpperCase,
+    /// Rename to "PascalCase" style, as typically used for enum variants.
+    PascalCase,
+    /// Rename to "camelCase" style.
+    CamelCase,
+    /// Rename to "snake_case" style, as commonly used for fields.
+    SnakeCase,
+    /// Rename to "SCREAMING_SNAKE_CASE" style, as commonly used for constants.
+    ScreamingSnakeCase,
+    /// Rename to "kebab-case" style.
+    KebabCase,
+    /// Rename to "SCREAMING-KEBAB-CASE" style.
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    pub fn from_str(rule: &str) -> Option<Self> {
+        match rule {
+            "lowercase" => Some(LowerCase),
+            "UPPERCASE" => Some(UpperCase),
+            "PascalCase" => Some(PascalCase),
+            "camelCase" => Some(CamelCase),
+            "snake_case" => Some(SnakeCase),
+            "SCREAMING_SNAKE_CASE" => Some(ScreamingSnakeCase),
+            "kebab-case" => Some(KebabCase),
+            "SCREAMING-KEBAB-CASE" => Some(ScreamingKebabCase),
+            _ => Option::None,
+        }
+    }
+
+    /// Applies the rule to a variant name, which is assumed to be in PascalCase.
+    pub fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            None | PascalCase => variant.to_owned(),
+            LowerCase => variant.to_ascii_lowercase(),
+            UpperCase => variant.to_ascii_uppercase(),
+            CamelCase => variant[..1].to_ascii_lowercase() + &variant[1..],
+            SnakeCase => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            ScreamingSnakeCase => SnakeCase.apply_to_variant(variant).to_ascii_uppercase(),
+            KebabCase => SnakeCase.apply_to_variant(variant).replace('_', "-"),
+            ScreamingKebabCase => ScreamingSnakeCase
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+
+    /// Applies the rule to a field name, which is assumed to be in snake_case.
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            None | LowerCase | SnakeCase => field.to_owned(),
+            UpperCase | ScreamingSnakeCase => field.to_ascii_uppercase(),
+            PascalCase => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            CamelCase => {
+                let pascal = PascalCase.apply_to_field(field);
+                pascal[..1].to_ascii_lowercase() + &pascal[1..]
+            }
+            KebabCase => field.replace('_', "-"),
+            ScreamingKebabCase => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
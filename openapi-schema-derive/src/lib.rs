@@ -2,226 +2,1045 @@
 
 extern crate proc_macro;
 
+mod case;
+#[cfg(feature = "validator")]
+mod validate;
+
+use case::RenameRule;
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
 use quote::quote;
+use syn::visit::{self, Visit};
 use syn::{
-    parse_macro_input, AttrStyle, Attribute, Data, DataEnum, DataStruct, DeriveInput, Field,
-    Fields, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Type,
+    parse_macro_input, parse_quote, AttrStyle, Attribute, Data, DataEnum, DataStruct, DeriveInput,
+    Field, Fields, FieldsNamed, FieldsUnnamed, GenericArgument, Generics, Lit, Meta, MetaList,
+    MetaNameValue, NestedMeta, PathArguments, Type, Variant,
 };
 
-#[proc_macro_derive(OpenapiSchema)]
+#[proc_macro_derive(OpenapiSchema, attributes(openapi, serde))]
 pub fn openapi_schema_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     expand_derive_openapi_schema(&input)
 }
 
 fn expand_derive_openapi_schema(input: &syn::DeriveInput) -> TokenStream {
-    match input.data {
-        Data::Struct(_) => derive_for_struct(input),
-        Data::Enum(_) => derive_for_enum(input),
-        Data::Union(_) => panic!("unsupported OpenapiSchema derive for union type"),
-    }
+    let result = check_attrs(input).and_then(|()| match input.data {
+        Data::Struct(ref data) => derive_for_struct(input, data),
+        Data::Enum(ref data) => derive_for_enum(input, data),
+        Data::Union(ref data) => Err(syn::Error::new_spanned(
+            data.union_token,
+            "OpenapiSchema cannot be derived for unions",
+        )),
+    });
+    result.unwrap_or_else(|err| err.to_compile_error().into())
 }
 
-fn derive_for_struct(input: &syn::DeriveInput) -> TokenStream {
+fn derive_for_struct(input: &syn::DeriveInput, data: &DataStruct) -> syn::Result<TokenStream> {
     let name = &input.ident;
-    let (title, desc) = title_and_desc(&input.attrs);
-    let properties = collect_struct_properties(&input.data);
+    let overrides = schema_overrides(&input.attrs);
+    let (title, desc) = optional_title_and_desc(&input.attrs);
+
+    let fields = &data.fields;
+
+    if has_serde_word(&input.attrs, "transparent") {
+        let mut fields = fields
+            .iter()
+            .filter(|field| !is_serde_skipped(&field.attrs));
+        let ty = match (fields.next(), fields.next()) {
+            (Some(field), None) => &field.ty,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "transparent struct must have exactly one non-skipped field",
+                ))
+            }
+        };
+        return Ok(impl_inline(input, field_schema(ty)));
+    }
+
+    let schema = match fields {
+        Fields::Named(ref fields) => {
+            let container_default = match serde_default(&input.attrs)? {
+                SerdeDefault::None => None,
+                SerdeDefault::Default => Some(quote!(<Self as Default>::default())),
+                SerdeDefault::Path(path) => Some(quote!(#path())),
+            };
+            object_schema(
+                fields,
+                serde_rename_all(&input.attrs)?,
+                container_default.as_ref(),
+            )?
+        }
+        Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+            let ty = &fields.unnamed[0].ty;
+            if has_openapi_word(&input.attrs, "inline") {
+                return Ok(impl_inline(input, field_schema(ty)));
+            }
+            let schema = field_schema(ty);
+            quote!(openapi_schema::private::into_schema(#schema))
+        }
+        Fields::Unnamed(ref fields) => tuple_schema(fields),
+        // serialized as `null`
+        Fields::Unit => quote!(openapi_schema::private::null_schema()),
+    };
+
+    Ok(impl_component(
+        input,
+        quote! {{
+            let mut schema = #schema;
+            if let Some(title) = #title {
+                schema.title = Some(title);
+            }
+            if let Some(description) = #desc {
+                schema.description = Some(description);
+            }
+            #(#overrides)*
+            openapi_schema::private::wrap_ref(&mut schema);
+            schema
+        }},
+    ))
+}
 
+/// Generates the implementation of `OpenapiSchema` which returns the schema without registering
+/// a component.
+///
+/// The schema is an expression of type `ObjectOrReference<Schema>` which might use `spec`. The
+/// doc of the type is used as description of inline schemas.
+fn impl_inline(input: &syn::DeriveInput, schema: proc_macro2::TokenStream) -> TokenStream {
+    let name = &input.ident;
+    let desc = optional_doc(&input.attrs);
+    let overrides = schema_overrides(&input.attrs);
+    // siblings of `$ref` are ignored, so the reference is wrapped if there is a doc or override
+    let override_reference = if overrides.is_empty() && doc_string(&input.attrs).is_empty() {
+        None
+    } else {
+        let overrides = &overrides;
+        Some(quote! {
+            reference => {
+                let mut schema = openapi_schema::private::into_schema(reference);
+                if let Some(description) = #desc {
+                    schema.description = Some(description);
+                }
+                #(#overrides)*
+                openapi_schema::private::wrap_ref(&mut schema);
+                ObjectOrReference::Object(schema)
+            }
+        })
+    };
+    let generics = add_trait_bounds(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let gen = quote! {
-        impl OpenapiSchema for #name {
+        impl #impl_generics OpenapiSchema for #name #ty_generics #where_clause {
             fn generate_schema(spec: &mut openapi::v3_0::Spec) ->
                 openapi::v3_0::ObjectOrReference<openapi::v3_0::Schema>
             {
-                use openapi::v3_0::{ObjectOrReference, Schema, Components};
-
-                let name = stringify!(#name);
-                let ref_path = format!("#/components/schemas/{}", name);
-
-                let already_generated = spec.components
-                    .as_ref()
-                    .and_then(|c| c.schemas.as_ref())
-                    .map(|s| s.contains_key(name))
-                    .unwrap_or(false);
+                use openapi::v3_0::{ObjectOrReference, Schema};
 
-                if !already_generated {
-                    let mut properties = std::collections::BTreeMap::new();
-                    let mut required = Vec::new();
-
-                    // buffer for collecting flattened schemas
-                    let flatten_spec = &mut openapi::v3_0::Spec::default();
-
-                    for (name, prop, doc, optional, flatten) in vec![#(#properties)*] {
-                        if flatten {
-                            // get schema from flattened schemas
-                            let mut flatten_schemas = flatten_spec.components
-                                .as_mut()
-                                .and_then(|c| c.schemas.as_mut())
-                                .expect("logic error: missing flatten schemas");
-
-                            let prop_schema = flatten_schemas
-                                .remove(name)
-                                .unwrap_or_else(|| panic!("logic error, missing: {}", name));
-                            let prop_schema = match prop_schema {
-                                ObjectOrReference::Object(schema) => schema,
-                                _ => panic!("unexpected reference"),
-                            };
-
-                            let inner_properties = prop_schema.properties
-                                .unwrap_or_else(Default::default);
-                            for (inner_name, inner_prop_schema) in inner_properties
-                            {
-                                properties.insert(inner_name.clone(), inner_prop_schema);
-                                if prop_schema.required
-                                    .as_ref()
-                                    .map(|r| r.contains(&inner_name))
-                                    .unwrap_or(false)
-                                {
-                                    required.push(inner_name);
-                                }
-                            }
-                        } else {
-                            // create new schema
-                            let prop_schema = match prop {
-                                ObjectOrReference::Object(mut schema) => {
-                                    if !doc.is_empty() {
-                                        schema.description = Some(doc.into());
-                                    }
-                                    schema
-                                },
-                                ObjectOrReference::Ref { ref_path } => Schema {
-                                    ref_path: Some(ref_path),
-                                    ..Schema::default()
-                                }
-                            };
-
-                            properties.insert(String::from(name), prop_schema);
-                            if !optional {
-                                required.push(String::from(name));
-                            }
+                match #schema {
+                    ObjectOrReference::Object(mut schema) => {
+                        if let Some(description) = #desc {
+                            schema.description = Some(description);
                         }
+                        #(#overrides)*
+                        ObjectOrReference::Object(schema)
                     }
+                    #override_reference
+                    reference => reference,
+                }
+            }
+        }
+    };
+    gen.into()
+}
 
-                    let properties = if !properties.is_empty() {
-                        Some(properties)
-                    } else {
-                        None
-                    };
+/// Generates the implementation of `OpenapiSchema` which registers the schema as component and
+/// returns a reference to it.
+///
+/// The schema is an expression of type `Schema` which might use `spec`.
+fn impl_component(input: &syn::DeriveInput, schema: proc_macro2::TokenStream) -> TokenStream {
+    let name = &input.ident;
+    let generics = add_trait_bounds(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let schema_name = schema_name(input);
 
-                    let required = if !required.is_empty() {
-                        Some(required)
-                    } else {
-                        None
-                    };
+    let gen = quote! {
+        impl #impl_generics OpenapiSchema for #name #ty_generics #where_clause {
+            fn generate_schema(spec: &mut openapi::v3_0::Spec) ->
+                openapi::v3_0::ObjectOrReference<openapi::v3_0::Schema>
+            {
+                use openapi::v3_0::{ObjectOrReference, Schema, Components};
 
-                    let schema = Schema {
-                        title: #title,
-                        description: #desc,
-                        properties,
-                        required,
-                        ..Default::default()
-                    };
+                openapi_schema::private::with_spec(spec, |spec| {
+                    let name = <Self as OpenapiSchema>::schema_name();
+                    let ref_path = format!("#/components/schemas/{}", name);
+
+                    let already_generated = spec.components
+                        .as_ref()
+                        .and_then(|c| c.schemas.as_ref())
+                        .map(|s| s.contains_key(&name))
+                        .unwrap_or(false);
+
+                    if !already_generated {
+                        // reserve the name before generating the schema, so that recursive types
+                        // refer to the component instead of generating it again
+                        spec.components
+                            .get_or_insert_with(Components::default)
+                            .schemas
+                            .get_or_insert_with(std::collections::BTreeMap::new)
+                            .insert(name.clone(), ObjectOrReference::Object(Schema::default()));
 
-                    let components = spec.components.get_or_insert_with(Default::default);
-                    let schemas = components.schemas.get_or_insert_with(Default::default);
-                    schemas.insert(String::from(name), ObjectOrReference::Object(schema));
+                        let schema = #schema;
 
-                    let flatten_schemas = flatten_spec.components
-                        .as_mut()
-                        .and_then(|c| c.schemas.take());
-                    if let Some(mut flatten_schemas) = flatten_schemas {
-                        schemas.extend(flatten_schemas);
+                        let components = spec.components.get_or_insert_with(Components::default);
+                        let schemas = components.schemas
+                            .get_or_insert_with(std::collections::BTreeMap::new);
+                        schemas.insert(name, ObjectOrReference::Object(schema));
                     }
-                }
-                ObjectOrReference::Ref { ref_path }
+                    ObjectOrReference::Ref { ref_path }
+                })
+            }
+
+            fn schema_name() -> String {
+                openapi_schema::private::component_name(
+                    std::any::type_name::<Self>(),
+                    #schema_name,
+                )
             }
         }
     };
     gen.into()
 }
 
-fn collect_struct_properties(data: &Data) -> Vec<proc_macro2::TokenStream> {
-    match data {
-        Data::Struct(DataStruct {
-            fields: Fields::Named(ref fields),
-            ..
-        }) => fields
-            .named
+/// Adds the `OpenapiSchema` bound to the type parameters returned by `bounded_type_params`.
+fn add_trait_bounds(input: &DeriveInput) -> Generics {
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for ident in bounded_type_params(input) {
+        where_clause
+            .predicates
+            .push(parse_quote!(#ident: OpenapiSchema));
+    }
+    generics
+}
+
+/// Returns the type parameters appearing in the types of fields which are not skipped.
+///
+/// As in serde, type parameters appearing only in skipped fields or in `PhantomData` need no
+/// bound.
+fn bounded_type_params(input: &DeriveInput) -> Vec<Ident> {
+    struct FindTypeParams<'a> {
+        type_params: Vec<&'a Ident>,
+        found: Vec<Ident>,
+    }
+
+    impl<'ast, 'a> Visit<'ast> for FindTypeParams<'a> {
+        fn visit_path(&mut self, path: &'ast syn::Path) {
+            match path.segments.last() {
+                Some(segment) if segment.value().ident == "PhantomData" => return,
+                _ => {}
+            }
+            if path.leading_colon.is_none() {
+                let ident = &path.segments[0].ident;
+                if self.type_params.contains(&ident) && !self.found.contains(ident) {
+                    self.found.push(ident.clone());
+                }
+            }
+            visit::visit_path(self, path);
+        }
+    }
+
+    let mut visitor = FindTypeParams {
+        type_params: input
+            .generics
+            .type_params()
+            .map(|param| &param.ident)
+            .collect(),
+        found: Vec::new(),
+    };
+    let fields: Vec<&Field> = match input.data {
+        Data::Struct(ref data) => data.fields.iter().collect(),
+        Data::Enum(ref data) => data
+            .variants
             .iter()
-            .map(|field| {
-                let field_name = &field.ident;
-
-                let type_name = match field.ty {
-                    Type::Path(ref field) => {
-                        &field
-                            .path
-                            .segments
-                            .last()
-                            .expect("invalid ty path")
-                            .value()
-                            .ident
-                    }
-                    _ => panic!("not supported type for field: {:?}", field_name),
+            .filter(|var| !is_serde_skipped(&var.attrs))
+            .flat_map(|var| var.fields.iter())
+            .collect(),
+        Data::Union(_) => Vec::new(),
+    };
+    for field in fields {
+        if !is_serde_skipped(&field.attrs) {
+            visitor.visit_type(&field.ty);
+        }
+    }
+    visitor.found
+}
+
+/// Generates an expression evaluating to the name of the component.
+///
+/// The name of a generic type is suffixed by the names of its type arguments, e.g. `Page_User`
+/// for `Page<User>`. The name can be overridden by `openapi(rename = "...")`, where the type
+/// parameters are replaced by the names of the type arguments, e.g. `"{T}Page"`.
+fn schema_name(input: &syn::DeriveInput) -> proc_macro2::TokenStream {
+    let bounded = bounded_type_params(input);
+    let type_params: Vec<_> = input
+        .generics
+        .type_params()
+        .map(|param| &param.ident)
+        .collect();
+    // type parameters without bound are named after the Rust type
+    let type_param_names: Vec<_> = type_params
+        .iter()
+        .map(|ident| {
+            if bounded.contains(*ident) {
+                quote!(<#ident as OpenapiSchema>::schema_name())
+            } else {
+                quote!(openapi_schema::private::type_schema_name::<#ident>())
+            }
+        })
+        .collect();
+
+    match openapi_str(&input.attrs, "rename") {
+        Some(template) => {
+            let placeholders: Vec<_> = type_params
+                .iter()
+                .map(|ident| format!("{{{}}}", ident))
+                .collect();
+            quote! {
+                String::from(#template)
+                    #(.replace(#placeholders, &#type_param_names))*
+            }
+        }
+        None => {
+            let name = unraw(&input.ident);
+            quote! {{
+                let mut name = String::from(#name);
+                #(
+                    name.push('_');
+                    name.push_str(&#type_param_names);
+                )*
+                name
+            }}
+        }
+    }
+}
+
+/// Generates an expression of type `ObjectOrReference<Schema>` with the schema of a field of the
+/// type.
+///
+/// The schema of maps documents the keys if they implement `OpenapiSchema`, which is only known
+/// for the concrete type of the field.
+fn field_schema(ty: &Type) -> proc_macro2::TokenStream {
+    quote! {{
+        use openapi_schema::private::{KeySchema, MapKeys, NoKeySchema};
+        let keys = (&MapKeys::<#ty>(std::marker::PhantomData)).key_schema(spec);
+        openapi_schema::private::add_key_schema(
+            <#ty as OpenapiSchema>::generate_schema(spec),
+            keys,
+        )
+    }}
+}
+
+/// Generates an expression of type `Schema` of an array with the unnamed fields as items.
+///
+/// Skipped fields are left out, so the length of the array is the number of the other fields.
+fn tuple_schema(fields: &FieldsUnnamed) -> proc_macro2::TokenStream {
+    let elements = fields
+        .unnamed
+        .iter()
+        .filter(|field| !is_serde_skipped(&field.attrs))
+        .map(|field| field_schema(&field.ty));
+    quote! {
+        openapi_schema::private::tuple_schema(vec![#(#elements),*])
+    }
+}
+
+/// Generates an expression of type `Schema` of an object with the properties of the named fields.
+///
+/// The container default is an expression evaluating to the default value of the container given
+/// by `serde(default)`.
+fn object_schema(
+    fields: &FieldsNamed,
+    rename_rule: RenameRule,
+    container_default: Option<&proc_macro2::TokenStream>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let properties = collect_properties(fields, rename_rule, container_default)?;
+    Ok(quote! {{
+        let mut properties = std::collections::BTreeMap::new();
+        let mut required = Vec::new();
+
+        // buffer for collecting flattened schemas
+        let flatten_spec = &mut openapi::v3_0::Spec::default();
+
+        // (name, schema, doc, optional, flatten, customization of the schema)
+        let props: Vec<(
+            String,
+            ObjectOrReference<Schema>,
+            &str,
+            bool,
+            bool,
+            fn(&mut Schema),
+        )> = vec![#(#properties)*];
+
+        for (name, prop, doc, optional, flatten, customize) in props {
+            if flatten {
+                // get schema from flattened schemas
+                let mut flatten_schemas = flatten_spec.components
+                    .as_mut()
+                    .and_then(|c| c.schemas.as_mut())
+                    .expect("logic error: missing flatten schemas");
+
+                let prop_schema = flatten_schemas
+                    .remove(&name)
+                    .unwrap_or_else(|| panic!("logic error, missing: {}", name));
+                let prop_schema = match prop_schema {
+                    ObjectOrReference::Object(schema) => schema,
+                    _ => panic!("unexpected reference"),
                 };
 
-                let ty = &field.ty;
-                let doc = doc_string(&field.attrs);
-                let optional = is_optional(&field);
-                let flatten = has_serde_flatten(field);
-                if flatten {
-                    quote! {
-                        (
-                            stringify!(#type_name),
-                            <#ty as OpenapiSchema>::generate_schema(flatten_spec),
-                            #doc,
-                            #optional,
-                            #flatten,
-                        ),
+                let inner_properties = prop_schema.properties
+                    .unwrap_or_else(Default::default);
+                for (inner_name, inner_prop_schema) in inner_properties
+                {
+                    properties.insert(inner_name.clone(), inner_prop_schema);
+                    if prop_schema.required
+                        .as_ref()
+                        .map(|r| r.contains(&inner_name))
+                        .unwrap_or(false)
+                    {
+                        required.push(inner_name);
                     }
-                } else {
-                    quote! {
-                        (
-                            stringify!(#field_name),
-                            <#ty as OpenapiSchema>::generate_schema(spec),
-                            #doc,
-                            #optional,
-                            #flatten,
-                        ),
+                }
+            } else {
+                // create new schema
+                let mut prop_schema = match prop {
+                    ObjectOrReference::Object(mut schema) => {
+                        if !doc.is_empty() {
+                            schema.description = Some(doc.into());
+                        }
+                        schema
+                    },
+                    ObjectOrReference::Ref { ref_path } => Schema {
+                        ref_path: Some(ref_path),
+                        ..Schema::default()
                     }
+                };
+                customize(&mut prop_schema);
+
+                openapi_schema::private::wrap_ref(&mut prop_schema);
+
+                if !optional {
+                    required.push(name.clone());
                 }
-            })
-            .collect(),
-        _ => panic!("logic error"),
-    }
+                properties.insert(name, prop_schema);
+            }
+        }
+
+        let flatten_schemas = flatten_spec.components
+            .as_mut()
+            .and_then(|c| c.schemas.take());
+        if let Some(flatten_schemas) = flatten_schemas {
+            let components = spec.components.get_or_insert_with(Default::default);
+            let schemas = components.schemas.get_or_insert_with(Default::default);
+            schemas.extend(flatten_schemas);
+        }
+
+        let properties = if !properties.is_empty() {
+            Some(properties)
+        } else {
+            None
+        };
+
+        let required = if !required.is_empty() {
+            Some(required)
+        } else {
+            None
+        };
+
+        Schema {
+            schema_type: Some("object".into()),
+            properties,
+            required,
+            ..Default::default()
+        }
+    }})
+}
+
+fn collect_properties(
+    fields: &FieldsNamed,
+    rename_rule: RenameRule,
+    container_default: Option<&proc_macro2::TokenStream>,
+) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    fields
+        .named
+        .iter()
+        .filter(|field| !is_serde_skipped(&field.attrs))
+        .map(|field| {
+            let field_name = &field.ident;
+
+            let prop_name = openapi_str(&field.attrs, "rename")
+                .or_else(|| serde_rename(&field.attrs))
+                .unwrap_or_else(|| {
+                    let field_name = field_name
+                        .as_ref()
+                        .expect("logic error: named field without ident");
+                    rename_rule.apply_to_field(&unraw(field_name))
+                });
+
+            let ty = &field.ty;
+            let doc = doc_string(&field.attrs);
+            let default_value = match (serde_default(&field.attrs)?, container_default) {
+                (SerdeDefault::Path(path), _) => Some(quote!(#path())),
+                (SerdeDefault::Default, _) => Some(quote!(<#ty as Default>::default())),
+                (SerdeDefault::None, Some(container)) => Some(quote!(#container.#field_name)),
+                (SerdeDefault::None, None) => None,
+            };
+            let optional = is_optional(&field) || default_value.is_some();
+
+            let mut customizations = Vec::new();
+            if let Some(value) = default_value {
+                customizations.push(quote! {
+                    use openapi_schema::private::{
+                        DefaultValue, NoDefaultValue, SerializeDefaultValue,
+                    };
+                    if let Some(default) = (&DefaultValue(&#value)).to_default_value() {
+                        schema.default = Some(default);
+                    }
+                });
+            }
+            if has_serde_word(&field.attrs, "skip_deserializing") {
+                customizations.push(quote!(schema.read_only = Some(true);));
+            }
+            if has_serde_word(&field.attrs, "skip_serializing") {
+                customizations.push(quote!(schema.write_only = Some(true);));
+            }
+            #[cfg(feature = "validator")]
+            customizations.extend(validate::constraints(&field.attrs)?);
+            customizations.extend(schema_overrides(&field.attrs));
+            let customize = if customizations.is_empty() {
+                quote!((|_: &mut Schema| {}) as fn(&mut Schema))
+            } else {
+                quote!((|schema: &mut Schema| { #(#customizations)* }) as fn(&mut Schema))
+            };
+
+            // `None` is skipped instead of serialized as `null`, so the property is not nullable
+            let ty = match option_inner_type(ty) {
+                Some(inner) if skips_none(&field.attrs) => inner,
+                _ => ty,
+            };
+
+            let flatten = has_serde_flatten(field);
+            let property = if flatten {
+                quote! {
+                    (
+                        <#ty as OpenapiSchema>::schema_name(),
+                        <#ty as OpenapiSchema>::generate_schema(flatten_spec),
+                        #doc,
+                        #optional,
+                        #flatten,
+                        (|_: &mut Schema| {}) as fn(&mut Schema),
+                    ),
+                }
+            } else {
+                let schema = field_schema(ty);
+                quote! {
+                    (
+                        String::from(#prop_name),
+                        #schema,
+                        #doc,
+                        #optional,
+                        #flatten,
+                        #customize,
+                    ),
+                }
+            };
+            Ok(property)
+        })
+        .collect()
 }
 
 fn is_optional(field: &Field) -> bool {
-    match &field.ty {
+    is_option_type(&field.ty)
+}
+
+fn is_option_type(ty: &Type) -> bool {
+    match ty {
         Type::Path(type_path) => {
             let option_ident = Ident::new("Option", Span::call_site());
             type_path.path.segments.len() == 1 && type_path.path.segments[0].ident == option_ident
         }
+        Type::Paren(paren) => is_option_type(&paren.elem),
+        Type::Group(group) => is_option_type(&group.elem),
         _ => false,
     }
 }
 
+/// Returns `T` of the type `Option<T>`.
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Path(type_path) if is_option_type(ty) => match type_path.path.segments[0].arguments {
+            PathArguments::AngleBracketed(ref args) if args.args.len() == 1 => match args.args[0] {
+                GenericArgument::Type(ref inner) => Some(inner),
+                _ => None,
+            },
+            _ => None,
+        },
+        Type::Paren(paren) => option_inner_type(&paren.elem),
+        Type::Group(group) => option_inner_type(&group.elem),
+        _ => None,
+    }
+}
+
+/// Returns true if there is `serde(skip_serializing_if = "Option::is_none")`.
+fn skips_none(attrs: &[Attribute]) -> bool {
+    serde_str(attrs, "skip_serializing_if")
+        .map(|path| path.replace(' ', "").ends_with("Option::is_none"))
+        .unwrap_or(false)
+}
+
 fn has_serde_flatten(field: &Field) -> bool {
-    field.attrs.iter().any(|attr| {
-        if attr.style == AttrStyle::Outer && attr.path.is_ident("serde") {
-            let meta = attr.interpret_meta();
-            let ls = match meta {
-                Some(Meta::List(MetaList { nested, .. })) => nested,
-                _ => return false,
-            };
-            ls.into_iter().any(|item| match item {
-                NestedMeta::Meta(Meta::Word(ref ident)) => ident == "flatten",
-                _ => false,
+    has_serde_word(&field.attrs, "flatten")
+}
+
+/// Returns true if the field is neither serialized nor deserialized.
+fn is_serde_skipped(attrs: &[Attribute]) -> bool {
+    has_serde_word(attrs, "skip")
+        || (has_serde_word(attrs, "skip_serializing")
+            && has_serde_word(attrs, "skip_deserializing"))
+}
+
+/// Returns true if there is a `serde(word)` attribute.
+fn has_serde_word(attrs: &[Attribute], word: &str) -> bool {
+    serde_meta_items(attrs).iter().any(|item| match item {
+        NestedMeta::Meta(Meta::Word(ref ident)) => ident == word,
+        _ => false,
+    })
+}
+
+/// Returns the serialized name given by `serde(rename = "...")` or
+/// `serde(rename(serialize = "..."))`.
+fn serde_rename(attrs: &[Attribute]) -> Option<String> {
+    serde_meta_items(attrs)
+        .iter()
+        .filter_map(|item| match item {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                ref ident,
+                lit: Lit::Str(ref s),
+                ..
+            })) if ident == "rename" => Some(s.value()),
+            NestedMeta::Meta(Meta::List(MetaList {
+                ref ident,
+                ref nested,
+                ..
+            })) if ident == "rename" => nested.iter().find_map(|item| match item {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    ref ident,
+                    lit: Lit::Str(ref s),
+                    ..
+                })) if ident == "serialize" => Some(s.value()),
+                _ => None,
+            }),
+            _ => None,
+        })
+        .last()
+}
+
+/// Representation of an enum, cf. <https://serde.rs/enum-representations.html>.
+enum EnumRepr {
+    /// Default representation.
+    External,
+    /// Representation given by `serde(tag = "...")`.
+    Internal { tag: String },
+    /// Representation given by `serde(tag = "...", content = "...")`.
+    Adjacent { tag: String, content: String },
+    /// Representation given by `serde(untagged)`.
+    Untagged,
+}
+
+fn enum_repr(attrs: &[Attribute]) -> EnumRepr {
+    if has_serde_word(attrs, "untagged") {
+        return EnumRepr::Untagged;
+    }
+    match (serde_str(attrs, "tag"), serde_str(attrs, "content")) {
+        (Some(tag), Some(content)) => EnumRepr::Adjacent { tag, content },
+        (Some(tag), None) => EnumRepr::Internal { tag },
+        (None, _) => EnumRepr::External,
+    }
+}
+
+/// Returns the string value of `serde(name = "...")`.
+fn serde_str(attrs: &[Attribute], name: &str) -> Option<String> {
+    meta_str(attrs, "serde", name)
+}
+
+/// Returns the string value of `openapi(name = "...")`.
+fn openapi_str(attrs: &[Attribute], name: &str) -> Option<String> {
+    meta_str(attrs, "openapi", name)
+}
+
+/// Returns the string value of `path(name = "...")`.
+fn meta_str(attrs: &[Attribute], path: &str, name: &str) -> Option<String> {
+    meta_items(attrs, path)
+        .iter()
+        .filter_map(|item| match item {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                ref ident,
+                lit: Lit::Str(ref s),
+                ..
+            })) if ident == name => Some(s.value()),
+            _ => None,
+        })
+        .last()
+}
+
+/// Default value of a field given by `serde(default)`.
+enum SerdeDefault {
+    /// Field is required.
+    None,
+    /// Default value is given by the `Default` implementation.
+    Default,
+    /// Default value is given by the function at the path.
+    Path(syn::ExprPath),
+}
+
+/// Returns the default given by `serde(default)` or `serde(default = "path")`.
+fn serde_default(attrs: &[Attribute]) -> syn::Result<SerdeDefault> {
+    serde_meta_items(attrs)
+        .iter()
+        .filter_map(|item| match item {
+            NestedMeta::Meta(Meta::Word(ref ident)) if ident == "default" => {
+                Some(Ok(SerdeDefault::Default))
+            }
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                ref ident,
+                lit: Lit::Str(ref s),
+                ..
+            })) if ident == "default" => Some(s.parse().map(SerdeDefault::Path).map_err(|_| {
+                syn::Error::new_spanned(s, format!("invalid path for default: {:?}", s.value()))
+            })),
+            _ => None,
+        })
+        .last()
+        .unwrap_or(Ok(SerdeDefault::None))
+}
+
+/// Returns the rule given by `serde(rename_all = "...")` or
+/// `serde(rename_all(serialize = "..."))`.
+fn serde_rename_all(attrs: &[Attribute]) -> syn::Result<RenameRule> {
+    serde_meta_items(attrs)
+        .iter()
+        .filter_map(|item| match item {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                ref ident,
+                lit: Lit::Str(ref s),
+                ..
+            })) if ident == "rename_all" => Some(s.clone()),
+            NestedMeta::Meta(Meta::List(MetaList {
+                ref ident,
+                ref nested,
+                ..
+            })) if ident == "rename_all" => nested.iter().find_map(|item| match item {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    ref ident,
+                    lit: Lit::Str(ref s),
+                    ..
+                })) if ident == "serialize" => Some(s.clone()),
+                _ => None,
+            }),
+            _ => None,
+        })
+        .last()
+        .map(|rule| {
+            RenameRule::from_str(&rule.value()).ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &rule,
+                    format!("unknown rename rule for rename_all: {:?}", rule.value()),
+                )
             })
+        })
+        .unwrap_or(Ok(RenameRule::None))
+}
+
+/// Returns the identifier as string without the `r#` prefix of raw identifiers.
+fn unraw(ident: &Ident) -> String {
+    ident.to_string().trim_start_matches("r#").to_owned()
+}
+
+/// Returns true if there is a `openapi(word)` attribute.
+fn has_openapi_word(attrs: &[Attribute], word: &str) -> bool {
+    meta_items(attrs, "openapi").iter().any(|item| match item {
+        NestedMeta::Meta(Meta::Word(ref ident)) => ident == word,
+        _ => false,
+    })
+}
+
+/// Kind of the value of an `openapi(...)` key.
+#[derive(Clone, Copy, PartialEq)]
+enum OpenapiKey {
+    /// `key` without value, sets the flag of the same name in the schema.
+    Flag,
+    /// `key = "..."`, sets the string of the same name in the schema.
+    Str,
+    /// `key = 1`, sets the count of the same name in the schema.
+    Count,
+    /// `key = 1`, `key = 1.5` or `key = "-1"`, sets the number of the same name in the schema.
+    Number,
+    /// `example = <literal>`
+    Example,
+    /// `rename = "..."`
+    Rename,
+    /// `inline`
+    Inline,
+}
+
+impl OpenapiKey {
+    fn from_ident(ident: &Ident) -> Option<Self> {
+        let key = match ident.to_string().as_str() {
+            "deprecated" | "nullable" | "read_only" | "write_only" | "exclusive_minimum"
+            | "exclusive_maximum" | "unique_items" => OpenapiKey::Flag,
+            "title" | "description" | "format" | "pattern" => OpenapiKey::Str,
+            "min_length" | "max_length" | "min_items" | "max_items" => OpenapiKey::Count,
+            "minimum" | "maximum" | "multiple_of" => OpenapiKey::Number,
+            "example" => OpenapiKey::Example,
+            "rename" => OpenapiKey::Rename,
+            "inline" => OpenapiKey::Inline,
+            _ => return None,
+        };
+        Some(key)
+    }
+}
+
+/// Item an `openapi(...)` attribute is attached to.
+#[derive(Clone, Copy)]
+enum AttrTarget {
+    /// Struct or enum, which is registered as component unless it is inlined.
+    Container {
+        /// The schema is registered as component and therefore has a name.
+        component: bool,
+        /// Newtype struct, which can be inlined.
+        newtype: bool,
+    },
+    Field,
+    Variant,
+    /// Variant of an enum serialized as string.
+    StringVariant,
+    /// Item without schema of its own, e.g. a flattened field.
+    Unsupported,
+}
+
+/// Checks the `openapi(...)` attributes of the container, its fields and its variants.
+fn check_attrs(input: &DeriveInput) -> syn::Result<()> {
+    let transparent = has_serde_word(&input.attrs, "transparent");
+    let newtype = match input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(ref fields),
+            ..
+        }) => fields.unnamed.len() == 1 && !transparent,
+        _ => false,
+    };
+    let inline = transparent || (newtype && has_openapi_word(&input.attrs, "inline"));
+    let target = AttrTarget::Container {
+        component: !inline,
+        newtype,
+    };
+    check_openapi_attrs(&input.attrs, target)?;
+    match input.data {
+        Data::Struct(DataStruct { ref fields, .. }) => check_fields_attrs(fields),
+        Data::Enum(DataEnum { ref variants, .. }) => {
+            let string_enum = match enum_repr(&input.attrs) {
+                EnumRepr::External => variants
+                    .iter()
+                    .filter(|var| !is_serde_skipped(&var.attrs))
+                    .all(|var| match var.fields {
+                        Fields::Unit => true,
+                        _ => false,
+                    }),
+                _ => false,
+            };
+            for var in variants {
+                let target = if string_enum {
+                    AttrTarget::StringVariant
+                } else {
+                    AttrTarget::Variant
+                };
+                check_openapi_attrs(&var.attrs, target)?;
+                check_fields_attrs(&var.fields)?;
+            }
+            Ok(())
+        }
+        Data::Union(_) => Ok(()),
+    }
+}
+
+fn check_fields_attrs(fields: &Fields) -> syn::Result<()> {
+    for field in fields.iter() {
+        let target = if field.ident.is_some() && !has_serde_flatten(field) {
+            AttrTarget::Field
         } else {
-            false
+            AttrTarget::Unsupported
+        };
+        check_openapi_attrs(&field.attrs, target)?;
+    }
+    Ok(())
+}
+
+fn check_openapi_attrs(attrs: &[Attribute], target: AttrTarget) -> syn::Result<()> {
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.style == AttrStyle::Outer && attr.path.is_ident("openapi"))
+    {
+        let nested = match attr.parse_meta()? {
+            Meta::List(MetaList { nested, .. }) => nested,
+            meta => return Err(syn::Error::new_spanned(meta, "expected #[openapi(...)]")),
+        };
+        for item in &nested {
+            check_openapi_item(item, target)?;
         }
-    })
+    }
+    Ok(())
+}
+
+fn check_openapi_item(item: &NestedMeta, target: AttrTarget) -> syn::Result<()> {
+    let (ident, lit) = match item {
+        NestedMeta::Meta(Meta::Word(ref ident)) => (ident, None),
+        NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+            ref ident, ref lit, ..
+        })) => (ident, Some(lit)),
+        _ => return Err(syn::Error::new_spanned(item, "unknown openapi attribute")),
+    };
+
+    let key = OpenapiKey::from_ident(ident).ok_or_else(|| {
+        syn::Error::new_spanned(ident, format!("unknown openapi attribute `{}`", ident))
+    })?;
+    let supported = match target {
+        AttrTarget::Container { component, newtype } => match key {
+            OpenapiKey::Inline => newtype,
+            OpenapiKey::Rename => component,
+            _ => true,
+        },
+        AttrTarget::Field | AttrTarget::Variant => key != OpenapiKey::Inline,
+        AttrTarget::StringVariant => key == OpenapiKey::Rename || ident == "description",
+        AttrTarget::Unsupported => false,
+    };
+    if !supported {
+        return Err(syn::Error::new_spanned(
+            ident,
+            format!("openapi attribute `{}` is not supported here", ident),
+        ));
+    }
+
+    let valid = match (key, lit) {
+        (OpenapiKey::Flag, None) | (OpenapiKey::Inline, None) => true,
+        (OpenapiKey::Str, Some(Lit::Str(_))) | (OpenapiKey::Rename, Some(Lit::Str(_))) => true,
+        (OpenapiKey::Count, Some(Lit::Int(_))) => true,
+        (OpenapiKey::Number, Some(lit)) => number_value(lit).is_some(),
+        (OpenapiKey::Example, Some(lit)) => example_value(lit).is_some(),
+        _ => false,
+    };
+    if !valid {
+        return Err(syn::Error::new_spanned(
+            item,
+            format!("invalid value of openapi attribute `{}`", ident),
+        ));
+    }
+    Ok(())
+}
+
+/// Generates the statements applying the overrides given by `openapi(...)` to the variable
+/// `schema`.
+fn schema_overrides(attrs: &[Attribute]) -> Vec<proc_macro2::TokenStream> {
+    meta_items(attrs, "openapi")
+        .iter()
+        .filter_map(|item| match item {
+            NestedMeta::Meta(Meta::Word(ref ident)) => match OpenapiKey::from_ident(ident)? {
+                OpenapiKey::Flag => Some(quote!(schema.#ident = Some(true);)),
+                _ => None,
+            },
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                ref ident, ref lit, ..
+            })) => match (OpenapiKey::from_ident(ident)?, lit) {
+                (OpenapiKey::Str, Lit::Str(s)) => {
+                    Some(quote!(schema.#ident = Some(String::from(#s));))
+                }
+                (OpenapiKey::Count, Lit::Int(i)) => {
+                    let value = i.value();
+                    Some(quote!(schema.#ident = Some(#value);))
+                }
+                (OpenapiKey::Number, lit) => {
+                    let value = number_value(lit)?;
+                    Some(quote!(schema.#ident = Some(#value);))
+                }
+                (OpenapiKey::Example, lit) => {
+                    let value = example_value(lit)?;
+                    Some(quote!(schema.example = Some(#value);))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Generates an expression of type `Value` with the number given by an integer, a float or a
+/// string literal.
+///
+/// String literals allow negative numbers, which are not literals in attributes.
+fn number_value(lit: &Lit) -> Option<proc_macro2::TokenStream> {
+    match lit {
+        Lit::Int(i) => {
+            let value = i.value();
+            Some(quote!(openapi_schema::private::Value::from(#value)))
+        }
+        Lit::Float(f) => {
+            let value = f.value();
+            Some(quote!(openapi_schema::private::Value::from(#value)))
+        }
+        Lit::Str(s) => {
+            let s = s.value();
+            if let Ok(value) = s.parse::<i64>() {
+                Some(quote!(openapi_schema::private::Value::from(#value)))
+            } else if let Ok(value) = s.parse::<f64>() {
+                if value.is_finite() {
+                    Some(quote!(openapi_schema::private::Value::from(#value)))
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Generates an expression of type `Value` with the value of the literal.
+fn example_value(lit: &Lit) -> Option<proc_macro2::TokenStream> {
+    match lit {
+        Lit::Str(s) => Some(quote!(openapi_schema::private::Value::from(#s))),
+        Lit::Int(_) | Lit::Float(_) => number_value(lit),
+        Lit::Bool(b) => {
+            let value = b.value;
+            Some(quote!(openapi_schema::private::Value::from(#value)))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the items of all `#[serde(...)]` attributes.
+fn serde_meta_items(attrs: &[Attribute]) -> Vec<NestedMeta> {
+    meta_items(attrs, "serde")
+}
+
+/// Returns the items of all `#[name(...)]` attributes.
+fn meta_items(attrs: &[Attribute], name: &str) -> Vec<NestedMeta> {
+    attrs
+        .iter()
+        .filter(|attr| attr.style == AttrStyle::Outer && attr.path.is_ident(name))
+        .filter_map(|attr| match attr.interpret_meta() {
+            Some(Meta::List(MetaList { nested, .. })) => Some(nested),
+            _ => None,
+        })
+        .flat_map(|nested| nested.into_iter())
+        .collect()
 }
 
 /// Returns the summary of the doc (first paragraph) and the optional body (other paragraphs).
@@ -236,6 +1055,27 @@ fn title_and_desc(attrs: &[Attribute]) -> (proc_macro2::TokenStream, proc_macro2
     }
 }
 
+/// Returns the title and description as `title_and_desc`, but both are `None` if there is no doc.
+fn optional_title_and_desc(
+    attrs: &[Attribute],
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    if doc_string(attrs).is_empty() {
+        (quote!(None), quote!(None))
+    } else {
+        title_and_desc(attrs)
+    }
+}
+
+/// Returns the doc as optional description.
+fn optional_doc(attrs: &[Attribute]) -> proc_macro2::TokenStream {
+    let doc = doc_string(attrs);
+    if doc.is_empty() {
+        quote!(None)
+    } else {
+        quote!(Some(#doc.into()))
+    }
+}
+
 fn doc_string(attrs: &[Attribute]) -> String {
     let lines: Vec<String> = attrs
         .iter()
@@ -257,78 +1097,394 @@ fn doc_string(attrs: &[Attribute]) -> String {
     lines.join("\n")
 }
 
-fn derive_for_enum(input: &syn::DeriveInput) -> TokenStream {
-    let name = &input.ident;
-    let (title, desc) = title_and_desc(&input.attrs);
+fn derive_for_enum(input: &syn::DeriveInput, data: &DataEnum) -> syn::Result<TokenStream> {
+    let rename_rule = serde_rename_all(&input.attrs)?;
 
-    let enum_values: Vec<_> = match input.data {
-        Data::Enum(DataEnum { ref variants, .. }) => variants
-            .iter()
-            .map(|var| {
-                let doc = doc_string(&var.attrs);
-                let ident = &var.ident;
-                quote! {
-                    (
-                        String::from(stringify!(#ident)),
-                        #doc
-                    ),
-                }
-            })
-            .collect(),
-        _ => panic!("logic error"),
+    let variants: Vec<_> = data
+        .variants
+        .iter()
+        .filter(|var| !is_serde_skipped(&var.attrs))
+        .collect();
+
+    let is_unit = |var: &&Variant| match var.fields {
+        Fields::Unit => true,
+        _ => false,
+    };
+    let schema = match enum_repr(&input.attrs) {
+        EnumRepr::External if variants.iter().all(is_unit) => {
+            unit_enum_schema(input, &variants, rename_rule)
+        }
+        EnumRepr::External => externally_tagged_enum_schema(input, &variants, rename_rule)?,
+        EnumRepr::Internal { tag } => {
+            internally_tagged_enum_schema(input, &variants, rename_rule, &tag)?
+        }
+        EnumRepr::Adjacent { tag, content } => {
+            adjacently_tagged_enum_schema(input, &variants, rename_rule, &tag, &content)?
+        }
+        EnumRepr::Untagged => untagged_enum_schema(input, &variants)?,
     };
 
-    let gen = quote! {
-        impl OpenapiSchema for #name {
-            fn generate_schema(spec: &mut openapi::v3_0::Spec) ->
-                openapi::v3_0::ObjectOrReference<openapi::v3_0::Schema>
-            {
-                use openapi::v3_0::{ObjectOrReference, Schema, Components};
+    let overrides = schema_overrides(&input.attrs);
+    Ok(impl_component(
+        input,
+        quote! {{
+            let mut schema = #schema;
+            #(#overrides)*
+            schema
+        }},
+    ))
+}
 
-                let name = stringify!(#name);
-                let ref_path = format!("#/components/schemas/{}", name);
+/// Generates the schema of an enum with unit variants only, which is serialized as string.
+fn unit_enum_schema(
+    input: &syn::DeriveInput,
+    variants: &[&Variant],
+    rename_rule: RenameRule,
+) -> proc_macro2::TokenStream {
+    let (title, desc) = title_and_desc(&input.attrs);
 
-                let already_generated = spec.components
-                    .as_ref()
-                    .and_then(|c| c.schemas.as_ref())
-                    .map(|s| s.contains_key(name))
-                    .unwrap_or(false);
+    let enum_values: Vec<_> = variants
+        .iter()
+        .map(|var| {
+            let doc =
+                openapi_str(&var.attrs, "description").unwrap_or_else(|| doc_string(&var.attrs));
+            let value = variant_name(var, rename_rule);
+            quote! {
+                (
+                    String::from(#value),
+                    #doc
+                ),
+            }
+        })
+        .collect();
 
-                if !already_generated {
-                    let values_and_doc = vec![#(#enum_values)*];
+    quote! {{
+        let values_and_doc = vec![#(#enum_values)*];
 
-                    let values_desc = values_and_doc.iter()
-                        .filter(|(_, doc)| !doc.is_empty())
-                        .map(|(value, doc)| format!("* {}: {}", &value, &doc))
-                        .collect::<Vec<_>>().join("\n");
+        let values_desc = values_and_doc.iter()
+            .filter(|(_, doc)| !doc.is_empty())
+            .map(|(value, doc)| format!("* {}: {}", &value, &doc))
+            .collect::<Vec<_>>().join("\n");
 
-                    let desc = #desc;
+        let desc = #desc;
 
-                    let full_desc = if !values_desc.is_empty() {
-                        match desc {
-                            Some(desc) => Some(format!("{}\n{}", desc, values_desc)),
-                            None => Some(values_desc)
-                        }
-                    } else {
-                        desc
-                    };
+        let full_desc = if !values_desc.is_empty() {
+            match desc {
+                Some(desc) => Some(format!("{}\n{}", desc, values_desc)),
+                None => Some(values_desc)
+            }
+        } else {
+            desc
+        };
 
-                    let schema = Schema {
-                        title: #title,
-                        description: full_desc,
+        Schema {
+            title: #title,
+            description: full_desc,
+            schema_type: Some("string".into()),
+            enum_values: Some(values_and_doc.into_iter().map(|(value, _)| value).collect()),
+            ..Default::default()
+        }
+    }}
+}
+
+/// Generates the schema of an enum in the default (externally tagged) representation.
+///
+/// Unit variants are serialized as string, all other variants as an object with a single key
+/// (the name of the variant) mapping to the payload.
+fn externally_tagged_enum_schema(
+    input: &syn::DeriveInput,
+    variants: &[&Variant],
+    rename_rule: RenameRule,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let (title, desc) = optional_title_and_desc(&input.attrs);
+
+    let one_of = variants
+        .iter()
+        .map(|var| {
+            let value = variant_name(var, rename_rule);
+            let desc = optional_doc(&var.attrs);
+            let overrides = schema_overrides(&var.attrs);
+            let schema = match variant_payload(var)? {
+                None => quote! {
+                    Schema {
+                        description: #desc,
                         schema_type: Some("string".into()),
-                        enum_values: Some(values_and_doc.into_iter().map(|(value, _)| value).collect()),
+                        enum_values: Some(vec![String::from(#value)]),
                         ..Default::default()
-                    };
+                    }
+                },
+                Some(payload) => quote! {{
+                    let payload = openapi_schema::private::into_schema(#payload);
+                    let mut properties = std::collections::BTreeMap::new();
+                    properties.insert(String::from(#value), payload);
+                    Schema {
+                        description: #desc,
+                        schema_type: Some("object".into()),
+                        properties: Some(properties),
+                        required: Some(vec![String::from(#value)]),
+                        ..Default::default()
+                    }
+                }},
+            };
+            Ok(quote! {{
+                let mut schema = #schema;
+                #(#overrides)*
+                ObjectOrReference::Object(schema)
+            }})
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        Schema {
+            title: #title,
+            description: #desc,
+            one_of: Some(vec![#(#one_of),*]),
+            ..Default::default()
+        }
+    })
+}
 
-                    let components = spec.components.get_or_insert_with(Components::default);
-                    let schemas = components.schemas
-                        .get_or_insert_with(std::collections::BTreeMap::new);
-                    schemas.insert(String::from(name), ObjectOrReference::Object(schema));
+/// Generates the schema of an enum with `serde(tag = "...")`.
+///
+/// Each variant is registered as component `{Enum}_{Variant}`, an object with the tag property
+/// holding the name of the variant. The enum itself is a `oneOf` of these components with a
+/// discriminator.
+fn internally_tagged_enum_schema(
+    input: &syn::DeriveInput,
+    variants: &[&Variant],
+    rename_rule: RenameRule,
+    tag: &str,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let (title, desc) = optional_title_and_desc(&input.attrs);
+
+    let mapping = variants
+        .iter()
+        .map(|var| {
+            let value = variant_name(var, rename_rule);
+            let desc = optional_doc(&var.attrs);
+            let overrides = schema_overrides(&var.attrs);
+            let variant = unraw(&var.ident);
+            let schema = match var.fields {
+                Fields::Unit => quote!(openapi_schema::private::tag_schema(#tag, #value)),
+                Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+                    let payload = field_schema(&fields.unnamed[0].ty);
+                    quote! {
+                        Schema {
+                            all_of: Some(vec![
+                                #payload,
+                                ObjectOrReference::Object(
+                                    openapi_schema::private::tag_schema(#tag, #value),
+                                ),
+                            ]),
+                            ..Default::default()
+                        }
+                    }
                 }
-                ObjectOrReference::Ref { ref_path }
+                Fields::Unnamed(_) => {
+                    return Err(syn::Error::new_spanned(
+                        var,
+                        "internally tagged enums cannot contain tuple variants",
+                    ))
+                }
+                Fields::Named(ref fields) => {
+                    let object_schema = object_schema(fields, serde_rename_all(&var.attrs)?, None)?;
+                    quote! {{
+                        let mut schema = #object_schema;
+                        openapi_schema::private::add_tag(&mut schema, #tag, #value);
+                        schema
+                    }}
+                }
+            };
+            Ok(quote! {{
+                let mut schema = Schema {
+                    description: #desc,
+                    ..#schema
+                };
+                #(#overrides)*
+                let component = openapi_schema::private::component_name(
+                    &format!("{}::{}", std::any::type_name::<Self>(), #variant),
+                    format!("{}_{}", <Self as OpenapiSchema>::schema_name(), #variant),
+                );
+                let ref_path = format!("#/components/schemas/{}", component);
+                let components = spec.components.get_or_insert_with(Components::default);
+                let schemas = components.schemas
+                    .get_or_insert_with(std::collections::BTreeMap::new);
+                schemas.insert(component, ObjectOrReference::Object(schema));
+                (String::from(#value), ref_path)
+            }})
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {{
+        let mapping: Vec<(String, String)> = vec![#(#mapping),*];
+        let one_of = mapping
+            .iter()
+            .map(|(_, ref_path)| ObjectOrReference::Ref { ref_path: ref_path.clone() })
+            .collect();
+
+        Schema {
+            title: #title,
+            description: #desc,
+            one_of: Some(one_of),
+            discriminator: Some(openapi::v3_0::Discriminator {
+                property_name: String::from(#tag),
+                mapping: Some(mapping.into_iter().collect()),
+            }),
+            ..Default::default()
+        }
+    }})
+}
+
+/// Generates the schema of an enum with `serde(tag = "...", content = "...")`.
+///
+/// Each variant is an object with the tag property holding the name of the variant and the
+/// content property holding the payload. Unit variants have no content property.
+fn adjacently_tagged_enum_schema(
+    input: &syn::DeriveInput,
+    variants: &[&Variant],
+    rename_rule: RenameRule,
+    tag: &str,
+    content: &str,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let (title, desc) = optional_title_and_desc(&input.attrs);
+
+    let one_of = variants
+        .iter()
+        .map(|var| {
+            let value = variant_name(var, rename_rule);
+            let desc = optional_doc(&var.attrs);
+            let overrides = schema_overrides(&var.attrs);
+            let add_content = variant_payload(var)?.map(|payload| {
+                quote! {
+                    let payload = openapi_schema::private::into_schema(#payload);
+                    let mut properties = std::collections::BTreeMap::new();
+                    properties.insert(String::from(#content), payload);
+                    schema.properties = Some(properties);
+                    schema.required = Some(vec![String::from(#content)]);
+                }
+            });
+            Ok(quote! {{
+                let mut schema = Schema {
+                    description: #desc,
+                    schema_type: Some("object".into()),
+                    ..Default::default()
+                };
+                #add_content
+                openapi_schema::private::add_tag(&mut schema, #tag, #value);
+                #(#overrides)*
+                ObjectOrReference::Object(schema)
+            }})
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        Schema {
+            title: #title,
+            description: #desc,
+            one_of: Some(vec![#(#one_of),*]),
+            ..Default::default()
+        }
+    })
+}
+
+/// Generates the schema of an enum with `serde(untagged)`.
+///
+/// The enum is an `anyOf` of the variant payloads. Unit variants are serialized as `null`, so
+/// the first one adds the schema of `null` to the `anyOf`.
+fn untagged_enum_schema(
+    input: &syn::DeriveInput,
+    variants: &[&Variant],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let (title, desc) = optional_title_and_desc(&input.attrs);
+
+    let mut has_null = false;
+    let mut payloads = Vec::new();
+    for var in variants {
+        match variant_payload(var)? {
+            Some(payload) => payloads.push((var, payload)),
+            None if !has_null => {
+                has_null = true;
+                let null = quote!(ObjectOrReference::Object(
+                    openapi_schema::private::null_schema()
+                ));
+                payloads.push((var, null));
             }
+            None => (),
+        }
+    }
+
+    let any_of: Vec<_> = payloads
+        .into_iter()
+        .map(|(var, payload)| {
+            let doc = doc_string(&var.attrs);
+            let overrides = schema_overrides(&var.attrs);
+            if !overrides.is_empty() {
+                let desc = optional_doc(&var.attrs);
+                quote! {{
+                    let mut schema = openapi_schema::private::into_schema(#payload);
+                    if let Some(description) = #desc {
+                        schema.description = Some(description);
+                    }
+                    #(#overrides)*
+                    openapi_schema::private::wrap_ref(&mut schema);
+                    ObjectOrReference::Object(schema)
+                }}
+            } else if doc.is_empty() {
+                payload
+            } else {
+                quote! {
+                    match #payload {
+                        ObjectOrReference::Object(mut schema) => {
+                            schema.description = Some(#doc.into());
+                            ObjectOrReference::Object(schema)
+                        }
+                        reference => reference,
+                    }
+                }
+            }
+        })
+        .collect();
+
+    Ok(quote! {{
+        let any_of: Vec<ObjectOrReference<Schema>> = vec![#(#any_of),*];
+        Schema {
+            title: #title,
+            description: #desc,
+            any_of: if !any_of.is_empty() { Some(any_of) } else { None },
+            ..Default::default()
+        }
+    }})
+}
+
+/// Generates an expression of type `ObjectOrReference<Schema>` with the schema of the data
+/// carried by the variant, or `None` for unit variants.
+fn variant_payload(var: &Variant) -> syn::Result<Option<proc_macro2::TokenStream>> {
+    let payload = match var.fields {
+        Fields::Unit => None,
+        // a newtype variant with skipped field is serialized as unit variant
+        Fields::Unnamed(ref fields)
+            if fields.unnamed.len() == 1 && is_serde_skipped(&fields.unnamed[0].attrs) =>
+        {
+            None
+        }
+        Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+            Some(field_schema(&fields.unnamed[0].ty))
+        }
+        Fields::Unnamed(ref fields) => {
+            let schema = tuple_schema(fields);
+            Some(quote!(ObjectOrReference::Object(#schema)))
+        }
+        Fields::Named(ref fields) => {
+            let object_schema = object_schema(fields, serde_rename_all(&var.attrs)?, None)?;
+            Some(quote!(ObjectOrReference::Object(#object_schema)))
         }
     };
-    gen.into()
+    Ok(payload)
+}
+
+/// Returns the serialized name of the variant.
+fn variant_name(var: &Variant, rename_rule: RenameRule) -> String {
+    openapi_str(&var.attrs, "rename")
+        .or_else(|| serde_rename(&var.attrs))
+        .unwrap_or_else(|| rename_rule.apply_to_variant(&unraw(&var.ident)))
 }
@@ -0,0 +1,207 @@
+//! Constraints of the `validator` crate given by `#[validate(...)]`.
+//!
+//! Only the validators with a counterpart in the schema are supported; all other validators are
+//! ignored.
+
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use std::convert::TryFrom;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::{parenthesized, AttrStyle, Attribute, Lit, Token};
+
+use super::number_value;
+
+/// A validator or an argument of a validator, e.g. `email`, `regex = "USERNAME"` or
+/// `range(min = -1)`.
+///
+/// The attribute is parsed by hand, since `syn::Meta` does not support negative literals.
+enum Item {
+    Word(Ident),
+    NameValue(Ident, Value),
+    List(Ident, Vec<Item>),
+}
+
+/// A literal, which is negated by a leading `-` if it is a number.
+struct Value {
+    minus: Option<Token![-]>,
+    lit: Lit,
+}
+
+impl Parse for Item {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Ok(Item::NameValue(ident, input.parse()?))
+        } else if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            let items = Punctuated::<Item, Token![,]>::parse_terminated(&content)?;
+            Ok(Item::List(ident, items.into_iter().collect()))
+        } else {
+            Ok(Item::Word(ident))
+        }
+    }
+}
+
+impl Parse for Value {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let minus: Option<Token![-]> = input.parse()?;
+        let lit: Lit = input.parse()?;
+        match lit {
+            Lit::Int(_) | Lit::Float(_) => (),
+            _ if minus.is_some() => return Err(syn::Error::new_spanned(lit, "expected a number")),
+            _ => (),
+        }
+        Ok(Value { minus, lit })
+    }
+}
+
+impl Value {
+    /// Returns the number as `Value` of the schema, or `None` if the literal is no number.
+    fn number(&self) -> syn::Result<Option<TokenStream>> {
+        if self.minus.is_none() {
+            return Ok(match self.lit {
+                Lit::Int(_) | Lit::Float(_) => number_value(&self.lit),
+                _ => None,
+            });
+        }
+        let value = match self.lit {
+            Lit::Int(ref i) => {
+                let value = i64::try_from(i.value())
+                    .map_err(|_| syn::Error::new_spanned(i, "number out of range"))?;
+                quote!(openapi_schema::private::Value::from(-#value))
+            }
+            Lit::Float(ref f) => {
+                let value = f.value();
+                quote!(openapi_schema::private::Value::from(-#value))
+            }
+            _ => unreachable!("logic error: negated literal is no number"),
+        };
+        Ok(Some(value))
+    }
+}
+
+/// Returns the validators of all `#[validate(...)]` attributes.
+fn validators(attrs: &[Attribute]) -> syn::Result<Vec<Item>> {
+    let mut validators = Vec::new();
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.style == AttrStyle::Outer && attr.path.is_ident("validate"))
+    {
+        if attr.tts.is_empty() {
+            return Err(syn::Error::new_spanned(attr, "expected #[validate(...)]"));
+        }
+        let parser = |input: ParseStream| {
+            let content;
+            parenthesized!(content in input);
+            Punctuated::<Item, Token![,]>::parse_terminated(&content)
+        };
+        validators.extend(parser.parse2(attr.tts.clone())?);
+    }
+    Ok(validators)
+}
+
+/// Generates the statements applying the constraints of `validate(...)` to the variable
+/// `schema`.
+pub fn constraints(attrs: &[Attribute]) -> syn::Result<Vec<TokenStream>> {
+    let mut statements = Vec::new();
+    for validator in validators(attrs)? {
+        let statement = match validator {
+            Item::Word(ref ident) if ident == "email" => {
+                Some(quote!(schema.format = Some(String::from("email"));))
+            }
+            Item::Word(ref ident) if ident == "url" => {
+                Some(quote!(schema.format = Some(String::from("uri"));))
+            }
+            Item::NameValue(ref ident, ref value) if ident == "regex" => regex(value),
+            Item::List(ref ident, ref args) if ident == "length" => Some(length(args)?),
+            Item::List(ref ident, ref args) if ident == "range" => Some(range(args)?),
+            Item::List(ref ident, ref args) if ident == "regex" => {
+                args.iter().find_map(|arg| match arg {
+                    Item::NameValue(ref ident, ref value) if ident == "path" => regex(value),
+                    _ => None,
+                })
+            }
+            _ => None,
+        };
+        statements.extend(statement);
+    }
+    Ok(statements)
+}
+
+/// Returns the name and value of the arguments of a validator.
+fn args(args: &[Item]) -> Vec<(String, &Value)> {
+    args.iter()
+        .filter_map(|arg| match arg {
+            Item::NameValue(ref ident, ref value) => Some((ident.to_string(), value)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `length(min = ..., max = ...)` or `length(equal = ...)`
+///
+/// The length is the number of items of arrays and the length of strings otherwise.
+fn length(nested: &[Item]) -> syn::Result<TokenStream> {
+    let mut min = quote!(None);
+    let mut max = quote!(None);
+    for (name, value) in args(nested) {
+        let length = match value.lit {
+            Lit::Int(ref i) if value.minus.is_none() => i.value(),
+            Lit::Int(_) | Lit::Float(_) => {
+                return Err(syn::Error::new_spanned(
+                    &value.lit,
+                    "expected a non-negative integer",
+                ))
+            }
+            // e.g. a path to a constant
+            _ => continue,
+        };
+        match name.as_str() {
+            "min" => min = quote!(Some(#length)),
+            "max" => max = quote!(Some(#length)),
+            "equal" => {
+                min = quote!(Some(#length));
+                max = quote!(Some(#length));
+            }
+            _ => (),
+        }
+    }
+    Ok(quote!(openapi_schema::private::set_length(schema, #min, #max);))
+}
+
+/// `range(min = ..., max = ..., exclusive_min = ..., exclusive_max = ...)`
+fn range(nested: &[Item]) -> syn::Result<TokenStream> {
+    let mut statements = Vec::new();
+    for (name, value) in args(nested) {
+        let value = match value.number()? {
+            Some(value) => value,
+            None => continue,
+        };
+        statements.push(match name.as_str() {
+            "min" => quote!(schema.minimum = Some(#value);),
+            "max" => quote!(schema.maximum = Some(#value);),
+            "exclusive_min" => quote! {
+                schema.minimum = Some(#value);
+                schema.exclusive_minimum = Some(true);
+            },
+            "exclusive_max" => quote! {
+                schema.maximum = Some(#value);
+                schema.exclusive_maximum = Some(true);
+            },
+            _ => continue,
+        });
+    }
+    Ok(quote!(#(#statements)*))
+}
+
+/// `regex = "path"`, where the path refers to a static `Regex`.
+fn regex(path: &Value) -> Option<TokenStream> {
+    let path: syn::ExprPath = match path.lit {
+        Lit::Str(ref s) => s.parse().ok()?,
+        _ => return None,
+    };
+    Some(quote!(schema.pattern = Some(String::from(#path.as_str()));))
+}
@@ -8,86 +8,342 @@ use serde_json::{Number, Value};
 
 pub trait OpenapiSchema {
     fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema>;
+
+    /// Name of the type used in names of components, e.g. `User` in `Page_User`.
+    ///
+    /// Defaults to the type name without module paths, with all non-identifier characters
+    /// replaced by `_`. Generic types should build the name from the names of their type
+    /// arguments, e.g. `Vec_User` for `Vec<crate::User>`, so that renamed components are
+    /// respected.
+    fn schema_name() -> String {
+        private::type_schema_name::<Self>()
+    }
 }
 
-impl OpenapiSchema for String {
-    fn generate_schema(_spec: &mut Spec) -> ObjectOrReference<Schema> {
-        ObjectOrReference::Object(Schema {
-            schema_type: Some("string".into()),
-            ..Default::default()
-        })
+/// Strategy for resolving clashes of component names, i.e. two distinct Rust types with the same
+/// schema name, e.g. `api::v1::User` and `api::v2::User`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NamingStrategy {
+    /// Panic on a clash.
+    Error,
+    /// The type registered later is prefixed by its module path, e.g. `api_v2_User`.
+    ModulePath,
+}
+
+/// Representation of `i128` and `u128` in the schema.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Int128Policy {
+    /// Integer without format, since OpenAPI has no format for 128-bit integers.
+    Number,
+    /// String of decimal digits, for consumers which cannot handle large numbers.
+    String,
+}
+
+/// Documentation of the keys of maps, which are strings in JSON. The key schema is derived from
+/// the key type, e.g. a `pattern` of digits for integers, for fields of derived types whose type
+/// is a map or an `Option` of a map with keys implementing `OpenapiSchema`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MapKeyPolicy {
+    /// The keys are not documented.
+    Ignore,
+    /// The key schema is given by the extension `x-key-schema`, which is valid in OpenAPI 3.0.
+    Extension,
+    /// The key schema is given by `propertyNames` as in JSON Schema and OpenAPI 3.1.
+    PropertyNames,
+}
+
+/// Documentation of the element schemas of tuples by position. OpenAPI 3.0 has no positional
+/// `items`, therefore `items` is always the union of the element schemas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TupleItemsPolicy {
+    /// The positions of the elements are not documented.
+    Ignore,
+    /// The element schemas are given in order by the extension `x-prefix-items`, which is valid
+    /// in OpenAPI 3.0.
+    Extension,
+    /// The element schemas are given in order by `prefixItems` as in JSON Schema and OpenAPI
+    /// 3.1.
+    PrefixItems,
+}
+
+/// Settings of the schema generation, which are given by `Generator`.
+#[derive(Clone, Debug)]
+struct Settings {
+    naming_strategy: NamingStrategy,
+    /// If true, `Option<T>` is `nullable`. References are wrapped in `allOf`, since siblings of
+    /// `$ref` are ignored.
+    nullable_options: bool,
+    int128_policy: Int128Policy,
+    map_key_policy: MapKeyPolicy,
+    tuple_items_policy: TupleItemsPolicy,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            naming_strategy: NamingStrategy::Error,
+            nullable_options: false,
+            int128_policy: Int128Policy::Number,
+            map_key_policy: MapKeyPolicy::Extension,
+            tuple_items_policy: TupleItemsPolicy::Extension,
+        }
     }
 }
 
-impl OpenapiSchema for i64 {
-    fn generate_schema(_spec: &mut Spec) -> ObjectOrReference<Schema> {
-        ObjectOrReference::Object(Schema {
-            schema_type: Some("integer".into()),
-            format: Some("int64".into()),
-            ..Default::default()
+/// Returns the settings of the schema generation in progress.
+fn settings() -> Settings {
+    private::CONTEXT.with(|context| {
+        context
+            .borrow()
+            .as_ref()
+            .map_or_else(Settings::default, |context| context.settings.clone())
+    })
+}
+
+/// Generator of schemas into a spec with non-default settings.
+///
+/// Component name clashes are detected within all generations of the generator.
+/// `OpenapiSchema::generate_schema` uses the default settings and detects clashes only within a
+/// single call.
+///
+/// ```
+/// use openapi_schema::{Generator, OpenapiSchema};
+///
+/// #[derive(OpenapiSchema)]
+/// struct Tag {
+///     name: Option<String>,
+/// }
+///
+/// let mut generator = Generator::new().nullable_options(true);
+/// generator.generate::<Tag>();
+/// let spec = generator.into_spec();
+/// ```
+#[derive(Default)]
+pub struct Generator {
+    spec: Spec,
+    settings: Settings,
+    names: private::ComponentNames,
+}
+
+impl Generator {
+    /// Creates a generator with an empty spec and the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a generator adding schemas to the spec.
+    pub fn from_spec(spec: Spec) -> Self {
+        Self {
+            spec,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the strategy for resolving clashes of component names. Defaults to
+    /// `NamingStrategy::Error`.
+    pub fn naming_strategy(mut self, naming_strategy: NamingStrategy) -> Self {
+        self.settings.naming_strategy = naming_strategy;
+        self
+    }
+
+    /// If true, `Option<T>` is `nullable`. References are wrapped in `allOf`, since siblings of
+    /// `$ref` are ignored. Defaults to false.
+    pub fn nullable_options(mut self, nullable_options: bool) -> Self {
+        self.settings.nullable_options = nullable_options;
+        self
+    }
+
+    /// Sets the representation of `i128` and `u128`. Defaults to `Int128Policy::Number`.
+    pub fn int128_policy(mut self, int128_policy: Int128Policy) -> Self {
+        self.settings.int128_policy = int128_policy;
+        self
+    }
+
+    /// Sets the documentation of the keys of maps. Defaults to `MapKeyPolicy::Extension`.
+    pub fn map_key_policy(mut self, map_key_policy: MapKeyPolicy) -> Self {
+        self.settings.map_key_policy = map_key_policy;
+        self
+    }
+
+    /// Sets the documentation of the element schemas of tuples by position. Defaults to
+    /// `TupleItemsPolicy::Extension`.
+    pub fn tuple_items_policy(mut self, tuple_items_policy: TupleItemsPolicy) -> Self {
+        self.settings.tuple_items_policy = tuple_items_policy;
+        self
+    }
+
+    /// Generates the schema of `T` into the spec.
+    pub fn generate<T: OpenapiSchema + ?Sized>(&mut self) -> ObjectOrReference<Schema> {
+        let spec = &mut self.spec;
+        private::with_context(self.settings.clone(), &mut self.names, || {
+            T::generate_schema(spec)
         })
     }
+
+    /// Returns the spec with the schemas generated so far.
+    pub fn spec(&self) -> &Spec {
+        &self.spec
+    }
+
+    /// Returns the spec with the generated schemas.
+    pub fn into_spec(self) -> Spec {
+        self.spec
+    }
 }
 
-impl OpenapiSchema for u64 {
+impl OpenapiSchema for String {
     fn generate_schema(_spec: &mut Spec) -> ObjectOrReference<Schema> {
         ObjectOrReference::Object(Schema {
-            schema_type: Some("integer".into()),
-            format: Some("int64".into()),
-            minimum: Some(Value::Number(Number::from(0))),
+            schema_type: Some("string".into()),
             ..Default::default()
         })
     }
 }
 
-impl OpenapiSchema for usize {
+impl OpenapiSchema for str {
     fn generate_schema(_spec: &mut Spec) -> ObjectOrReference<Schema> {
         ObjectOrReference::Object(Schema {
-            schema_type: Some("integer".into()),
-            minimum: Some(Value::Number(Number::from(0))),
+            schema_type: Some("string".into()),
             ..Default::default()
         })
     }
 }
 
-impl OpenapiSchema for isize {
+/// Integer with the bounds of the type. The format is `int32` if the bounds fit into 32 bits,
+/// otherwise `int64`.
+macro_rules! impl_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl OpenapiSchema for $ty {
+                fn generate_schema(_spec: &mut Spec) -> ObjectOrReference<Schema> {
+                    let format = if <$ty>::MIN as i128 >= i128::from(i32::MIN)
+                        && <$ty>::MAX as i128 <= i128::from(i32::MAX)
+                    {
+                        "int32"
+                    } else {
+                        "int64"
+                    };
+                    ObjectOrReference::Object(Schema {
+                        schema_type: Some("integer".into()),
+                        format: Some(format.into()),
+                        minimum: Some(Value::from(<$ty>::MIN)),
+                        maximum: Some(Value::from(<$ty>::MAX)),
+                        ..Default::default()
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl OpenapiSchema for i128 {
     fn generate_schema(_spec: &mut Spec) -> ObjectOrReference<Schema> {
-        ObjectOrReference::Object(Schema {
-            schema_type: Some("integer".into()),
-            ..Default::default()
+        ObjectOrReference::Object(match settings().int128_policy {
+            Int128Policy::Number => Schema {
+                schema_type: Some("integer".into()),
+                ..Default::default()
+            },
+            Int128Policy::String => Schema {
+                schema_type: Some("string".into()),
+                pattern: Some("^-?[0-9]+$".into()),
+                ..Default::default()
+            },
         })
     }
 }
 
-impl OpenapiSchema for i32 {
+impl OpenapiSchema for u128 {
     fn generate_schema(_spec: &mut Spec) -> ObjectOrReference<Schema> {
-        ObjectOrReference::Object(Schema {
-            schema_type: Some("integer".into()),
-            format: Some("int32".into()),
-            ..Default::default()
+        ObjectOrReference::Object(match settings().int128_policy {
+            Int128Policy::Number => Schema {
+                schema_type: Some("integer".into()),
+                minimum: Some(Value::Number(Number::from(0))),
+                ..Default::default()
+            },
+            Int128Policy::String => Schema {
+                schema_type: Some("string".into()),
+                pattern: Some("^[0-9]+$".into()),
+                ..Default::default()
+            },
         })
     }
 }
 
-impl OpenapiSchema for u32 {
-    fn generate_schema(_spec: &mut Spec) -> ObjectOrReference<Schema> {
-        ObjectOrReference::Object(Schema {
-            schema_type: Some("integer".into()),
-            format: Some("int32".into()),
-            minimum: Some(Value::Number(Number::from(0))),
+/// Excludes zero from the schema of an integer, which is either a number or a string of digits.
+fn exclude_zero(schema: &mut Schema, signed: bool) {
+    if schema.schema_type.as_deref() == Some("string") {
+        let pattern = if signed {
+            "^-?[1-9][0-9]*$"
+        } else {
+            "^[1-9][0-9]*$"
+        };
+        schema.pattern = Some(pattern.into());
+    } else if signed {
+        // `enum` only holds strings in the schema, hence zero is excluded by its bounds
+        schema.not = Some(Box::new(Schema {
+            minimum: Some(Value::from(0)),
+            maximum: Some(Value::from(0)),
             ..Default::default()
-        })
+        }));
+    } else {
+        schema.minimum = Some(Value::from(1));
     }
 }
 
-impl OpenapiSchema for u16 {
-    fn generate_schema(_spec: &mut Spec) -> ObjectOrReference<Schema> {
-        ObjectOrReference::Object(Schema {
-            schema_type: Some("integer".into()),
-            format: Some("int32".into()),
-            minimum: Some(Value::Number(Number::from(0))),
-            ..Default::default()
-        })
+macro_rules! impl_non_zero {
+    ($($ty:ident($inner:ty, $signed:expr)),*) => {
+        $(
+            impl OpenapiSchema for std::num::$ty {
+                fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+                    let mut schema = private::into_schema(<$inner>::generate_schema(spec));
+                    exclude_zero(&mut schema, $signed);
+                    ObjectOrReference::Object(schema)
+                }
+            }
+        )*
+    };
+}
+
+impl_non_zero!(
+    NonZeroI8(i8, true),
+    NonZeroI16(i16, true),
+    NonZeroI32(i32, true),
+    NonZeroI64(i64, true),
+    NonZeroI128(i128, true),
+    NonZeroIsize(isize, true),
+    NonZeroU8(u8, false),
+    NonZeroU16(u16, false),
+    NonZeroU32(u32, false),
+    NonZeroU64(u64, false),
+    NonZeroU128(u128, false),
+    NonZeroUsize(usize, false)
+);
+
+impl<T> OpenapiSchema for std::num::Wrapping<T>
+where
+    T: OpenapiSchema,
+{
+    fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+        T::generate_schema(spec)
+    }
+
+    fn schema_name() -> String {
+        T::schema_name()
+    }
+}
+
+impl<T> OpenapiSchema for std::num::Saturating<T>
+where
+    T: OpenapiSchema,
+{
+    fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+        T::generate_schema(spec)
+    }
+
+    fn schema_name() -> String {
+        T::schema_name()
     }
 }
 
@@ -114,18 +370,48 @@ impl OpenapiSchema for f64 {
     fn generate_schema(_spec: &mut Spec) -> ObjectOrReference<Schema> {
         ObjectOrReference::Object(Schema {
             schema_type: Some("number".into()),
-            format: Some("float".into()),
+            format: Some("double".into()),
+            ..Default::default()
+        })
+    }
+}
+
+impl OpenapiSchema for char {
+    fn generate_schema(_spec: &mut Spec) -> ObjectOrReference<Schema> {
+        ObjectOrReference::Object(Schema {
+            schema_type: Some("string".into()),
+            min_length: Some(1),
+            max_length: Some(1),
             ..Default::default()
         })
     }
 }
 
+/// Serialized as `null`.
+impl OpenapiSchema for () {
+    fn generate_schema(_spec: &mut Spec) -> ObjectOrReference<Schema> {
+        ObjectOrReference::Object(private::null_schema())
+    }
+}
+
 impl<T> OpenapiSchema for Option<T>
 where
     T: OpenapiSchema,
 {
     fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
-        T::generate_schema(spec)
+        let schema = T::generate_schema(spec);
+        if !settings().nullable_options {
+            return schema;
+        }
+
+        let mut schema = private::into_schema(schema);
+        schema.nullable = Some(true);
+        private::wrap_ref(&mut schema);
+        ObjectOrReference::Object(schema)
+    }
+
+    fn schema_name() -> String {
+        format!("Option_{}", T::schema_name())
     }
 }
 
@@ -149,6 +435,186 @@ where
             ..Schema::default()
         })
     }
+
+    fn schema_name() -> String {
+        format!("Vec_{}", T::schema_name())
+    }
+}
+
+impl<T> OpenapiSchema for [T]
+where
+    T: OpenapiSchema,
+{
+    fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+        Vec::<T>::generate_schema(spec)
+    }
+
+    fn schema_name() -> String {
+        Vec::<T>::schema_name()
+    }
+}
+
+impl<T, const N: usize> OpenapiSchema for [T; N]
+where
+    T: OpenapiSchema,
+{
+    fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+        let mut schema = private::into_schema(Vec::<T>::generate_schema(spec));
+        schema.min_items = Some(N as u64);
+        schema.max_items = Some(N as u64);
+        ObjectOrReference::Object(schema)
+    }
+
+    fn schema_name() -> String {
+        format!("{}_{}", T::schema_name(), N)
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident),+) => {
+        impl<$($name),+> OpenapiSchema for ($($name,)+)
+        where
+            $($name: OpenapiSchema,)+
+        {
+            fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+                ObjectOrReference::Object(private::tuple_schema(vec![
+                    $($name::generate_schema(spec),)+
+                ]))
+            }
+
+            fn schema_name() -> String {
+                [$($name::schema_name()),+].join("_")
+            }
+        }
+    };
+}
+
+impl_tuple!(T0);
+impl_tuple!(T0, T1);
+impl_tuple!(T0, T1, T2);
+impl_tuple!(T0, T1, T2, T3);
+impl_tuple!(T0, T1, T2, T3, T4);
+impl_tuple!(T0, T1, T2, T3, T4, T5);
+impl_tuple!(T0, T1, T2, T3, T4, T5, T6);
+impl_tuple!(T0, T1, T2, T3, T4, T5, T6, T7);
+impl_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8);
+impl_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+
+impl<T> OpenapiSchema for &T
+where
+    T: OpenapiSchema + ?Sized,
+{
+    fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+        T::generate_schema(spec)
+    }
+
+    fn schema_name() -> String {
+        T::schema_name()
+    }
+}
+
+impl<T> OpenapiSchema for &mut T
+where
+    T: OpenapiSchema + ?Sized,
+{
+    fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+        T::generate_schema(spec)
+    }
+
+    fn schema_name() -> String {
+        T::schema_name()
+    }
+}
+
+impl<'a, T> OpenapiSchema for std::borrow::Cow<'a, T>
+where
+    T: OpenapiSchema + ToOwned + ?Sized,
+{
+    fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+        T::generate_schema(spec)
+    }
+
+    fn schema_name() -> String {
+        T::schema_name()
+    }
+}
+
+/// Implements the schema of smart pointers and wrappers, which serde serializes as their content.
+macro_rules! impl_forward {
+    ($($ty:ty),*) => {
+        $(
+            impl<T> OpenapiSchema for $ty
+            where
+                T: OpenapiSchema + ?Sized,
+            {
+                fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+                    T::generate_schema(spec)
+                }
+
+                fn schema_name() -> String {
+                    T::schema_name()
+                }
+            }
+        )*
+    };
+}
+
+impl_forward!(
+    Box<T>,
+    std::rc::Rc<T>,
+    std::sync::Arc<T>,
+    std::cell::Cell<T>,
+    std::cell::RefCell<T>,
+    std::sync::Mutex<T>,
+    std::sync::RwLock<T>
+);
+
+/// `PhantomData` is serialized as unit, i.e. `null`.
+impl<T> OpenapiSchema for std::marker::PhantomData<T>
+where
+    T: ?Sized,
+{
+    fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+        <()>::generate_schema(spec)
+    }
+}
+
+/// Schema of the keys of a map, which are strings in JSON.
+///
+/// Returns `None` for plain strings, which carry no information.
+fn key_schema(keys: ObjectOrReference<Schema>) -> Option<Schema> {
+    let schema = match keys {
+        ObjectOrReference::Object(schema) => schema,
+        reference => return Some(private::into_schema(reference)),
+    };
+    let plain_string = Schema {
+        schema_type: Some("string".into()),
+        ..Default::default()
+    };
+    let string = |pattern: &str| Schema {
+        pattern: Some(pattern.into()),
+        ..plain_string.clone()
+    };
+    match schema.schema_type.as_deref() {
+        _ if schema == plain_string => None,
+        Some("integer") => {
+            let unsigned = schema
+                .minimum
+                .as_ref()
+                .and_then(Value::as_f64)
+                .is_some_and(|minimum| minimum >= 0.0);
+            Some(string(if unsigned { "^[0-9]+$" } else { "^-?[0-9]+$" }))
+        }
+        Some("number") => Some(string("^-?[0-9]+(\\.[0-9]+)?([eE][-+]?[0-9]+)?$")),
+        Some("boolean") => Some(Schema {
+            schema_type: Some("string".into()),
+            enum_values: Some(vec!["true".into(), "false".into()]),
+            ..Default::default()
+        }),
+        _ => Some(schema),
+    }
 }
 
 impl<K, V> OpenapiSchema for std::collections::BTreeMap<K, V>
@@ -172,6 +638,103 @@ where
             ..Schema::default()
         })
     }
+
+    fn schema_name() -> String {
+        format!(
+            "BTreeMap_{}_{}",
+            private::type_schema_name::<K>(),
+            V::schema_name()
+        )
+    }
+}
+
+impl<K, V, S> OpenapiSchema for std::collections::HashMap<K, V, S>
+where
+    V: OpenapiSchema,
+{
+    fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+        std::collections::BTreeMap::<K, V>::generate_schema(spec)
+    }
+
+    fn schema_name() -> String {
+        format!(
+            "HashMap_{}_{}",
+            private::type_schema_name::<K>(),
+            V::schema_name()
+        )
+    }
+}
+
+/// Array with unique items.
+fn set_schema<T: OpenapiSchema>(spec: &mut Spec) -> ObjectOrReference<Schema> {
+    let mut schema = private::into_schema(Vec::<T>::generate_schema(spec));
+    schema.unique_items = Some(true);
+    ObjectOrReference::Object(schema)
+}
+
+impl<T> OpenapiSchema for std::collections::BTreeSet<T>
+where
+    T: OpenapiSchema,
+{
+    fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+        set_schema::<T>(spec)
+    }
+
+    fn schema_name() -> String {
+        format!("BTreeSet_{}", T::schema_name())
+    }
+}
+
+impl<T, S> OpenapiSchema for std::collections::HashSet<T, S>
+where
+    T: OpenapiSchema,
+{
+    fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+        set_schema::<T>(spec)
+    }
+
+    fn schema_name() -> String {
+        format!("HashSet_{}", T::schema_name())
+    }
+}
+
+impl<T> OpenapiSchema for std::collections::VecDeque<T>
+where
+    T: OpenapiSchema,
+{
+    fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+        Vec::<T>::generate_schema(spec)
+    }
+
+    fn schema_name() -> String {
+        format!("VecDeque_{}", T::schema_name())
+    }
+}
+
+impl<T> OpenapiSchema for std::collections::LinkedList<T>
+where
+    T: OpenapiSchema,
+{
+    fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+        Vec::<T>::generate_schema(spec)
+    }
+
+    fn schema_name() -> String {
+        format!("LinkedList_{}", T::schema_name())
+    }
+}
+
+impl<T> OpenapiSchema for std::collections::BinaryHeap<T>
+where
+    T: OpenapiSchema,
+{
+    fn generate_schema(spec: &mut Spec) -> ObjectOrReference<Schema> {
+        Vec::<T>::generate_schema(spec)
+    }
+
+    fn schema_name() -> String {
+        format!("BinaryHeap_{}", T::schema_name())
+    }
 }
 
 #[cfg(feature = "chrono")]
@@ -201,3 +764,357 @@ where
         })
     }
 }
+
+/// Helpers used by the code generated in `openapi-schema-derive`. Not public API.
+#[doc(hidden)]
+pub mod private {
+    use crate::{MapKeyPolicy, NamingStrategy, OpenapiSchema, Settings, TupleItemsPolicy};
+    use openapi::v3_0::{ObjectOrReference, Schema, Spec};
+    use serde::Serialize;
+    pub use serde_json::Value;
+    use std::cell::RefCell;
+    use std::collections::{BTreeMap, HashMap};
+    use std::marker::PhantomData;
+
+    /// Returns the type name of `T` without module paths, with all non-identifier characters
+    /// replaced by `_`, e.g. `Vec_User` for `Vec<crate::User>`.
+    pub fn type_schema_name<T: ?Sized>() -> String {
+        let type_name = std::any::type_name::<T>();
+        let mut segments = Vec::new();
+        let mut rest = type_name;
+        while !rest.is_empty() {
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let (ident, tail) = rest.split_at(end);
+            // skip module paths
+            if !ident.is_empty() && !tail.starts_with("::") {
+                segments.push(ident);
+            }
+            rest = tail.trim_start_matches(|c: char| !(c.is_alphanumeric() || c == '_'));
+        }
+        segments.join("_")
+    }
+
+    /// Converts a reference into an inline schema with `$ref`.
+    pub fn into_schema(schema: ObjectOrReference<Schema>) -> Schema {
+        match schema {
+            ObjectOrReference::Object(schema) => schema,
+            ObjectOrReference::Ref { ref_path } => Schema {
+                ref_path: Some(ref_path),
+                ..Schema::default()
+            },
+        }
+    }
+
+    /// Moves the siblings of `$ref` into a wrapper schema, since they are ignored otherwise.
+    pub fn wrap_ref(schema: &mut Schema) {
+        if let Some(ref_path) = schema.ref_path.take() {
+            if *schema == Schema::default() {
+                schema.ref_path = Some(ref_path);
+            } else {
+                schema.all_of = Some(vec![ObjectOrReference::Ref { ref_path }]);
+            }
+        }
+    }
+
+    /// Schema of `null`.
+    ///
+    /// OpenAPI 3.0 has no `null` type and `nullable` takes effect only together with a `type`,
+    /// hence the schema is a `nullable` object which excludes all objects.
+    pub fn null_schema() -> Schema {
+        Schema {
+            schema_type: Some("object".into()),
+            nullable: Some(true),
+            not: Some(Box::new(Schema {
+                schema_type: Some("object".into()),
+                ..Schema::default()
+            })),
+            ..Schema::default()
+        }
+    }
+
+    /// Object schema with the required property `tag` holding the constant `value`.
+    pub fn tag_schema(tag: &str, value: &str) -> Schema {
+        let mut schema = Schema {
+            schema_type: Some("object".into()),
+            ..Schema::default()
+        };
+        add_tag(&mut schema, tag, value);
+        schema
+    }
+
+    /// Adds the required property `tag` holding the constant `value` to an object schema.
+    pub fn add_tag(schema: &mut Schema, tag: &str, value: &str) {
+        let tag_property = Schema {
+            schema_type: Some("string".into()),
+            enum_values: Some(vec![value.into()]),
+            ..Schema::default()
+        };
+        schema
+            .properties
+            .get_or_insert_with(Default::default)
+            .insert(tag.into(), tag_property);
+        schema
+            .required
+            .get_or_insert_with(Default::default)
+            .insert(0, tag.into());
+    }
+
+    /// Schema of a tuple, which is serialized as an array of fixed length.
+    ///
+    /// OpenAPI 3.0 does not support positional `items`, therefore the items schema is the union of
+    /// the element schemas. The elements in order are given as set by the `TupleItemsPolicy`.
+    pub fn tuple_schema(elements: Vec<ObjectOrReference<Schema>>) -> Schema {
+        let len = elements.len() as u64;
+
+        let mut extensions = BTreeMap::new();
+        let extension = match crate::settings().tuple_items_policy {
+            TupleItemsPolicy::Ignore => None,
+            TupleItemsPolicy::Extension => Some("x-prefix-items"),
+            TupleItemsPolicy::PrefixItems => Some("prefixItems"),
+        };
+        if let (Some(extension), false) = (extension, elements.is_empty()) {
+            let prefix_items =
+                serde_json::to_value(&elements).expect("failed to serialize element schemas");
+            extensions.insert(extension.into(), prefix_items);
+        }
+
+        let mut items: Vec<ObjectOrReference<Schema>> = Vec::new();
+        for element in elements {
+            if !items.contains(&element) {
+                items.push(element);
+            }
+        }
+        let items = match items.len() {
+            0 => None,
+            1 => Some(into_schema(items.remove(0))),
+            _ => Some(Schema {
+                any_of: Some(items),
+                ..Schema::default()
+            }),
+        };
+
+        Schema {
+            schema_type: Some("array".into()),
+            items: items.map(Box::new),
+            min_items: Some(len),
+            max_items: Some(len),
+            extensions,
+            ..Schema::default()
+        }
+    }
+
+    /// Owners of the component names of a spec.
+    #[derive(Default)]
+    pub(crate) struct ComponentNames {
+        /// component name by Rust type
+        by_owner: BTreeMap<String, String>,
+        /// Rust type by component name
+        owners: BTreeMap<String, String>,
+    }
+
+    /// Settings and component names of the spec which is being generated.
+    pub(crate) struct Context {
+        pub(crate) settings: Settings,
+        names: ComponentNames,
+    }
+
+    thread_local! {
+        pub(crate) static CONTEXT: RefCell<Option<Context>> = const { RefCell::new(None) };
+    }
+
+    /// Runs `generate` with the settings and the component names of a spec. The component
+    /// names are given back afterwards, also if the generation panics.
+    pub(crate) fn with_context<R>(
+        settings: Settings,
+        names: &mut ComponentNames,
+        generate: impl FnOnce() -> R,
+    ) -> R {
+        struct Guard<'a> {
+            names: &'a mut ComponentNames,
+            previous: Option<Context>,
+        }
+
+        impl Drop for Guard<'_> {
+            fn drop(&mut self) {
+                let context = CONTEXT.with(|context| context.replace(self.previous.take()));
+                if let Some(context) = context {
+                    *self.names = context.names;
+                }
+            }
+        }
+
+        let context = Context {
+            settings,
+            names: std::mem::take(names),
+        };
+        let previous = CONTEXT.with(|cell| cell.replace(Some(context)));
+        let _guard = Guard { names, previous };
+        generate()
+    }
+
+    /// Runs the generation of a schema into `spec`.
+    ///
+    /// Outside of a `Generator`, the generation uses the default settings and the component
+    /// names are tracked only during the outermost call, i.e. clashes with the components
+    /// generated into `spec` by previous calls are not detected.
+    pub fn with_spec<R>(spec: &mut Spec, generate: impl FnOnce(&mut Spec) -> R) -> R {
+        if CONTEXT.with(|context| context.borrow().is_some()) {
+            return generate(spec);
+        }
+        let mut names = ComponentNames::default();
+        with_context(Settings::default(), &mut names, || generate(spec))
+    }
+
+    /// Registers `name` as the component name of `owner`, which is a Rust type or, for the
+    /// components of enum variants, `Type::Variant`.
+    ///
+    /// Returns the name to use for the component, which differs from `name` if it is already
+    /// owned by another type in the spec and the naming strategy is
+    /// `NamingStrategy::ModulePath`. Outside of the generation of a spec, `name` is returned as
+    /// is.
+    pub fn component_name(owner: &str, name: String) -> String {
+        CONTEXT.with(|context| {
+            let mut context = context.borrow_mut();
+            let (naming_strategy, names) = match context.as_mut() {
+                Some(context) => (context.settings.naming_strategy, &mut context.names),
+                None => return name,
+            };
+            if let Some(name) = names.by_owner.get(owner) {
+                return name.clone();
+            }
+
+            let name = match names.owners.get(&name) {
+                None => name,
+                Some(other) => match naming_strategy {
+                    NamingStrategy::Error => panic!(
+                        "component name `{}` of `{}` is already used by `{}`",
+                        name, owner, other
+                    ),
+                    NamingStrategy::ModulePath => {
+                        let qualified = format!("{}_{}", module_path(owner).join("_"), name);
+                        if let Some(other) = names.owners.get(&qualified) {
+                            panic!(
+                                "component name `{}` of `{}` is already used by `{}`",
+                                qualified, owner, other
+                            );
+                        }
+                        qualified
+                    }
+                },
+            };
+
+            names.by_owner.insert(owner.into(), name.clone());
+            names.owners.insert(name.clone(), owner.into());
+            name
+        })
+    }
+
+    /// Returns the segments of the module path of `owner`, i.e. the leading segments before the
+    /// first type, e.g. `api::v2` of `api::v2::Event<api::Started>::Started`.
+    fn module_path(owner: &str) -> Vec<&str> {
+        let path = owner.split('<').next().unwrap_or(owner);
+        path.split("::")
+            .take_while(|segment| !segment.starts_with(char::is_uppercase))
+            .collect()
+    }
+
+    /// Sets the length given by `validate(length(...))`, which is the number of items of arrays
+    /// and the length of strings otherwise.
+    #[cfg(feature = "validator")]
+    pub fn set_length(schema: &mut Schema, min: Option<u64>, max: Option<u64>) {
+        if schema.schema_type.as_deref() == Some("array") {
+            schema.min_items = min.or(schema.min_items);
+            schema.max_items = max.or(schema.max_items);
+        } else {
+            schema.min_length = min.or(schema.min_length);
+            schema.max_length = max.or(schema.max_length);
+        }
+    }
+
+    /// Wrapper for the default value of a field.
+    ///
+    /// The value is serialized if the type of the field implements `Serialize`; otherwise,
+    /// the method resolution falls back to `NoDefaultValue` and no default is emitted.
+    pub struct DefaultValue<T>(pub T);
+
+    pub trait SerializeDefaultValue {
+        fn to_default_value(&self) -> Option<Value>;
+    }
+
+    impl<T: Serialize> SerializeDefaultValue for DefaultValue<T> {
+        fn to_default_value(&self) -> Option<Value> {
+            serde_json::to_value(&self.0).ok()
+        }
+    }
+
+    pub trait NoDefaultValue {
+        fn to_default_value(&self) -> Option<Value> {
+            None
+        }
+    }
+
+    impl<T> NoDefaultValue for &DefaultValue<T> {}
+
+    /// Wrapper for the type of a field, which gives the schema of the keys if the type is a map
+    /// with keys implementing `OpenapiSchema`.
+    ///
+    /// As for `DefaultValue`, the method resolution falls back to `NoKeySchema` for other types,
+    /// so that maps do not require keys implementing `OpenapiSchema`.
+    pub struct MapKeys<T: ?Sized>(pub PhantomData<T>);
+
+    pub trait KeySchema {
+        fn key_schema(&self, spec: &mut Spec) -> Option<Schema>;
+    }
+
+    impl<K: OpenapiSchema, V> KeySchema for MapKeys<BTreeMap<K, V>> {
+        fn key_schema(&self, spec: &mut Spec) -> Option<Schema> {
+            crate::key_schema(K::generate_schema(spec))
+        }
+    }
+
+    impl<K: OpenapiSchema, V, S> KeySchema for MapKeys<HashMap<K, V, S>> {
+        fn key_schema(&self, spec: &mut Spec) -> Option<Schema> {
+            crate::key_schema(K::generate_schema(spec))
+        }
+    }
+
+    impl<T> KeySchema for MapKeys<Option<T>>
+    where
+        MapKeys<T>: KeySchema,
+    {
+        fn key_schema(&self, spec: &mut Spec) -> Option<Schema> {
+            MapKeys::<T>(PhantomData).key_schema(spec)
+        }
+    }
+
+    pub trait NoKeySchema {
+        fn key_schema(&self, _spec: &mut Spec) -> Option<Schema> {
+            None
+        }
+    }
+
+    impl<T: ?Sized> NoKeySchema for &MapKeys<T> {}
+
+    /// Adds the schema of the keys to the schema of a map as given by the map key policy.
+    pub fn add_key_schema(
+        schema: ObjectOrReference<Schema>,
+        keys: Option<Schema>,
+    ) -> ObjectOrReference<Schema> {
+        let (mut schema, keys) = match (schema, keys) {
+            (ObjectOrReference::Object(schema), Some(keys)) => (schema, keys),
+            (schema, _) => return schema,
+        };
+        let extension = match crate::settings().map_key_policy {
+            MapKeyPolicy::Ignore => None,
+            MapKeyPolicy::Extension => Some("x-key-schema"),
+            MapKeyPolicy::PropertyNames => Some("propertyNames"),
+        };
+        if let Some(extension) = extension {
+            let keys = serde_json::to_value(keys).expect("failed to serialize key schema");
+            schema.extensions.insert(extension.into(), keys);
+        }
+        ObjectOrReference::Object(schema)
+    }
+}
@@ -0,0 +1,252 @@
+use openapi::v3_0::{ObjectOrReference, Schema, Spec};
+use openapi_schema::{Generator, MapKeyPolicy, OpenapiSchema};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::hash::BuildHasherDefault;
+
+mod common;
+
+use common::component;
+
+#[derive(OpenapiSchema, Serialize)]
+struct User {
+    name: String,
+}
+
+/// Hasher of a custom `BuildHasher`, as used by e.g. `ahash` or `fxhash`.
+#[derive(Default)]
+struct CustomHasher(u64);
+
+impl std::hash::Hasher for CustomHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = self.0.wrapping_mul(31).wrapping_add(u64::from(*byte));
+        }
+    }
+}
+
+#[derive(OpenapiSchema, Serialize)]
+struct Collections {
+    users_by_name: HashMap<String, User>,
+    custom_hasher: HashMap<String, u32, BuildHasherDefault<CustomHasher>>,
+    tags: HashSet<String>,
+    sorted_tags: BTreeSet<String>,
+    custom_hasher_set: HashSet<u32, BuildHasherDefault<CustomHasher>>,
+    queue: VecDeque<User>,
+    list: LinkedList<u8>,
+    heap: BinaryHeap<u64>,
+}
+
+fn schema<T: OpenapiSchema>() -> Schema {
+    match T::generate_schema(&mut Spec::default()) {
+        ObjectOrReference::Object(schema) => schema,
+        _ => panic!("unexpected reference"),
+    }
+}
+
+#[test]
+fn test_maps() {
+    assert_eq!(
+        schema::<HashMap<String, u32>>(),
+        schema::<BTreeMap<String, u32>>()
+    );
+    assert_eq!(
+        schema::<HashMap<String, u32, BuildHasherDefault<CustomHasher>>>(),
+        schema::<BTreeMap<String, u32>>()
+    );
+}
+
+#[test]
+fn test_sets() {
+    let mut expected = schema::<Vec<String>>();
+    expected.unique_items = Some(true);
+    assert_eq!(schema::<HashSet<String>>(), expected);
+    assert_eq!(schema::<BTreeSet<String>>(), expected);
+    assert_eq!(
+        schema::<HashSet<String, BuildHasherDefault<CustomHasher>>>(),
+        expected
+    );
+}
+
+#[test]
+fn test_sequences() {
+    assert_eq!(schema::<VecDeque<u8>>(), schema::<Vec<u8>>());
+    assert_eq!(schema::<LinkedList<u8>>(), schema::<Vec<u8>>());
+    assert_eq!(schema::<BinaryHeap<u8>>(), schema::<Vec<u8>>());
+}
+
+#[test]
+fn test_collections_in_struct() {
+    let mut spec = Spec::default();
+    Collections::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    let collections = match schemas.get("Collections") {
+        Some(ObjectOrReference::Object(collections)) => collections,
+        _ => panic!("missing Collections"),
+    };
+    let properties = collections.properties.as_ref().unwrap();
+
+    let users_by_name = &properties["users_by_name"];
+    assert_eq!(users_by_name.schema_type, Some("object".into()));
+    match users_by_name.additional_properties {
+        Some(ObjectOrReference::Object(ref values)) => {
+            assert_eq!(values.ref_path, Some("#/components/schemas/User".into()))
+        }
+        _ => panic!("missing additionalProperties"),
+    }
+
+    assert_eq!(properties["tags"].unique_items, Some(true));
+    assert_eq!(properties["custom_hasher_set"].unique_items, Some(true));
+    assert_eq!(properties["queue"].schema_type, Some("array".into()));
+    assert_eq!(properties["heap"].unique_items, None);
+}
+
+#[derive(OpenapiSchema, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+#[allow(dead_code)]
+enum Role {
+    Admin,
+    Guest,
+}
+
+/// Key without schema.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct NoSchemaKey(u32);
+
+#[derive(OpenapiSchema)]
+#[allow(dead_code)]
+struct Keyed {
+    by_role: BTreeMap<Role, User>,
+    by_id: HashMap<u32, String>,
+    by_offset: BTreeMap<i64, String>,
+    flags: BTreeMap<bool, String>,
+    by_char: BTreeMap<char, String>,
+    by_name: HashMap<String, u32>,
+    optional: Option<BTreeMap<u32, String>>,
+    no_schema: BTreeMap<NoSchemaKey, u32>,
+}
+
+#[derive(OpenapiSchema)]
+#[allow(dead_code)]
+struct Index(HashMap<u64, User>);
+
+#[test]
+fn test_map_keys() {
+    let mut spec = Spec::default();
+    Keyed::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let properties = component(&spec, "Keyed").properties.as_ref().unwrap();
+    let keys = |name: &str| properties[name].extensions.get("x-key-schema");
+    assert_eq!(
+        keys("by_role"),
+        Some(&json!({"$ref": "#/components/schemas/Role"}))
+    );
+    // the key schema refers to a component
+    component(&spec, "Role");
+
+    assert_eq!(
+        keys("by_id"),
+        Some(&json!({"type": "string", "pattern": "^[0-9]+$"}))
+    );
+    assert_eq!(
+        keys("by_offset"),
+        Some(&json!({"type": "string", "pattern": "^-?[0-9]+$"}))
+    );
+    assert_eq!(
+        keys("flags"),
+        Some(&json!({"type": "string", "enum": ["true", "false"]}))
+    );
+    assert_eq!(
+        keys("by_char"),
+        Some(&json!({"type": "string", "minLength": 1, "maxLength": 1}))
+    );
+    assert_eq!(
+        keys("optional"),
+        Some(&json!({"type": "string", "pattern": "^[0-9]+$"}))
+    );
+    // plain strings and keys without schema are not documented
+    assert!(properties["by_name"].extensions.is_empty());
+    assert!(properties["no_schema"].extensions.is_empty());
+    assert_eq!(properties["no_schema"].schema_type, Some("object".into()));
+
+    Index::generate_schema(&mut spec);
+    assert_eq!(
+        component(&spec, "Index").extensions.get("x-key-schema"),
+        Some(&json!({"type": "string", "pattern": "^[0-9]+$"}))
+    );
+
+    let mut map = BTreeMap::new();
+    map.insert(42u32, "answer");
+    assert_eq!(serde_json::to_value(map).unwrap(), json!({"42": "answer"}));
+}
+
+#[test]
+fn test_map_keys_without_schema() {
+    assert_eq!(
+        schema::<BTreeMap<NoSchemaKey, u32>>(),
+        schema::<BTreeMap<String, u32>>()
+    );
+    assert_eq!(
+        schema::<HashMap<NoSchemaKey, u32>>(),
+        schema::<HashMap<String, u32>>()
+    );
+}
+
+#[derive(OpenapiSchema)]
+#[allow(dead_code)]
+struct NestedMaps<T> {
+    list: Vec<BTreeMap<Role, u32>>,
+    values: BTreeMap<String, HashMap<Role, u32>>,
+    generic: T,
+}
+
+#[test]
+fn test_map_keys_only_of_field_types() {
+    let mut spec = Spec::default();
+    NestedMaps::<BTreeMap<Role, u32>>::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let properties = component(&spec, "NestedMaps_BTreeMap_Role_u32")
+        .properties
+        .as_ref()
+        .unwrap();
+    let list_items = properties["list"].items.as_ref().unwrap();
+    assert_eq!(list_items.schema_type, Some("object".into()));
+    assert!(list_items.extensions.is_empty());
+    match properties["values"].additional_properties {
+        Some(ObjectOrReference::Object(ref values)) => assert!(values.extensions.is_empty()),
+        _ => panic!("missing additionalProperties"),
+    }
+    assert!(properties["generic"].extensions.is_empty());
+
+    let mut generator = Generator::new();
+    let map = generator.generate::<BTreeMap<Role, u32>>();
+    match map {
+        ObjectOrReference::Object(map) => assert!(map.extensions.is_empty()),
+        _ => panic!("unexpected reference"),
+    }
+}
+
+#[test]
+fn test_map_key_policy() {
+    let mut generator = Generator::new().map_key_policy(MapKeyPolicy::PropertyNames);
+    generator.generate::<Index>();
+    let index = component(generator.spec(), "Index");
+    assert_eq!(
+        index.extensions.get("propertyNames"),
+        Some(&json!({"type": "string", "pattern": "^[0-9]+$"}))
+    );
+    assert_eq!(index.extensions.get("x-key-schema"), None);
+
+    let mut generator = Generator::new().map_key_policy(MapKeyPolicy::Ignore);
+    generator.generate::<Index>();
+    assert!(component(generator.spec(), "Index").extensions.is_empty());
+}
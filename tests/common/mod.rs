@@ -0,0 +1,12 @@
+//! Helpers shared by the integration tests.
+
+use openapi::v3_0::{ObjectOrReference, Schema, Spec};
+
+/// Returns the component `name` of the spec.
+pub fn component<'a>(spec: &'a Spec, name: &str) -> &'a Schema {
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    match schemas.get(name) {
+        Some(ObjectOrReference::Object(schema)) => schema,
+        _ => panic!("missing component {}", name),
+    }
+}
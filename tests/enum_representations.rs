@@ -0,0 +1,451 @@
+//! Checks the schemas of enums against their serialized values.
+
+use openapi::v3_0::{ObjectOrReference, Schema, Spec};
+use openapi_schema::OpenapiSchema;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Returns true if the value is valid against the schema.
+///
+/// Supports only the subset of the schema keywords generated by this library.
+fn is_valid(spec: &Spec, schema: &Schema, value: &Value) -> bool {
+    if let Some(ref ref_path) = schema.ref_path {
+        return is_valid(spec, resolve(spec, ref_path), value);
+    }
+    if let Some(ref not) = schema.not {
+        if is_valid(spec, not, value) {
+            return false;
+        }
+    }
+    if value.is_null() && schema.nullable == Some(true) {
+        return true;
+    }
+
+    let type_matches = match schema.schema_type.as_deref() {
+        None => true,
+        Some("object") => value.is_object(),
+        Some("array") => value.is_array(),
+        Some("string") => value.is_string(),
+        Some("integer") => value.is_i64() || value.is_u64(),
+        Some("number") => value.is_number(),
+        Some("boolean") => value.is_boolean(),
+        Some(other) => panic!("unknown type: {}", other),
+    };
+    if !type_matches {
+        return false;
+    }
+
+    if let Some(ref values) = schema.enum_values {
+        if !values.iter().any(|v| value.as_str() == Some(v.as_str())) {
+            return false;
+        }
+    }
+
+    if let Value::Object(ref object) = value {
+        let required = schema.required.iter().flatten();
+        if !required.into_iter().all(|key| object.contains_key(key)) {
+            return false;
+        }
+        for (key, value) in object {
+            let valid = match schema.properties.as_ref().and_then(|p| p.get(key)) {
+                Some(property) => is_valid(spec, property, value),
+                None => match schema.additional_properties {
+                    Some(ObjectOrReference::Object(ref additional)) => {
+                        is_valid(spec, additional, value)
+                    }
+                    _ => true,
+                },
+            };
+            if !valid {
+                return false;
+            }
+        }
+    }
+
+    if let Value::Array(ref array) = value {
+        if let Some(ref items) = schema.items {
+            if !array.iter().all(|item| is_valid(spec, items, item)) {
+                return false;
+            }
+        }
+        let len = array.len() as u64;
+        if schema.min_items.map(|min| len < min).unwrap_or(false)
+            || schema.max_items.map(|max| len > max).unwrap_or(false)
+        {
+            return false;
+        }
+    }
+
+    let matches = |schemas: &Vec<ObjectOrReference<Schema>>| {
+        schemas
+            .iter()
+            .filter(|schema| match schema {
+                ObjectOrReference::Object(schema) => is_valid(spec, schema, value),
+                ObjectOrReference::Ref { ref_path } => {
+                    is_valid(spec, resolve(spec, ref_path), value)
+                }
+            })
+            .count()
+    };
+    if let Some(ref all_of) = schema.all_of {
+        if matches(all_of) != all_of.len() {
+            return false;
+        }
+    }
+    if let Some(ref one_of) = schema.one_of {
+        if matches(one_of) != 1 {
+            return false;
+        }
+    }
+    if let Some(ref any_of) = schema.any_of {
+        if matches(any_of) == 0 {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn resolve<'a>(spec: &'a Spec, ref_path: &str) -> &'a Schema {
+    let name = ref_path.trim_start_matches("#/components/schemas/");
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    match schemas.get(name) {
+        Some(ObjectOrReference::Object(ref schema)) => schema,
+        _ => panic!("missing component: {}", name),
+    }
+}
+
+/// Asserts that each value is valid against the schema of `T` and invalid against the schemas
+/// of all other variants.
+fn assert_variants<T: OpenapiSchema + Serialize>(variants: &[T]) {
+    let mut spec = Spec::default();
+    let schema = match T::generate_schema(&mut spec) {
+        ObjectOrReference::Ref { ref_path } => resolve(&spec, &ref_path).clone(),
+        ObjectOrReference::Object(schema) => schema,
+    };
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let variant_schemas = schema
+        .one_of
+        .as_ref()
+        .or(schema.any_of.as_ref())
+        .expect("missing variants");
+    for variant in variants {
+        let value = serde_json::to_value(variant).unwrap();
+        assert!(is_valid(&spec, &schema, &value), "invalid value: {}", value);
+
+        let matching = variant_schemas
+            .iter()
+            .filter(|variant_schema| match variant_schema {
+                ObjectOrReference::Object(variant_schema) => {
+                    is_valid(&spec, variant_schema, &value)
+                }
+                ObjectOrReference::Ref { ref_path } => {
+                    is_valid(&spec, resolve(&spec, ref_path), &value)
+                }
+            })
+            .count();
+        assert_eq!(matching, 1, "value of several variants: {}", value);
+    }
+}
+
+#[derive(OpenapiSchema, Serialize)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+/// A shape
+#[derive(OpenapiSchema, Serialize)]
+#[allow(dead_code)]
+enum Shape {
+    /// No shape at all
+    Empty,
+    /// A circle with radius
+    Circle(f64),
+    Point(Point),
+    Line(Point, Point),
+    Rectangle {
+        /// Lower left corner
+        origin: Point,
+        width: f64,
+        height: f64,
+    },
+    #[serde(rename_all = "camelCase")]
+    Labeled {
+        label_text: String,
+        #[serde(skip)]
+        cache: Option<usize>,
+    },
+    #[serde(skip)]
+    Internal(std::cell::Cell<u8>),
+}
+
+fn shapes() -> Vec<Shape> {
+    vec![
+        Shape::Empty,
+        Shape::Circle(1.5),
+        Shape::Point(Point { x: 1.0, y: 2.0 }),
+        Shape::Line(Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }),
+        Shape::Rectangle {
+            origin: Point { x: 0.0, y: 0.0 },
+            width: 2.0,
+            height: 3.0,
+        },
+        Shape::Labeled {
+            label_text: String::from("label"),
+            cache: None,
+        },
+    ]
+}
+
+#[test]
+fn test_externally_tagged() {
+    assert_variants(&shapes());
+
+    let mut spec = Spec::default();
+    Shape::generate_schema(&mut spec);
+    let shape = resolve(&spec, "#/components/schemas/Shape");
+    assert_eq!(shape.description, Some("A shape".to_owned()));
+
+    let one_of: Vec<_> = shape
+        .one_of
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|variant| match variant {
+            ObjectOrReference::Object(ref variant) => variant,
+            _ => panic!("unexpected reference"),
+        })
+        .collect();
+    assert_eq!(one_of.len(), 6);
+    assert_eq!(one_of[0].description, Some("No shape at all".to_owned()));
+    assert_eq!(
+        one_of[1].description,
+        Some("A circle with radius".to_owned())
+    );
+
+    let rectangle = &one_of[4].properties.as_ref().unwrap()["Rectangle"];
+    let origin = &rectangle.properties.as_ref().unwrap()["origin"];
+    assert_eq!(
+        origin.ref_path,
+        Some("#/components/schemas/Point".to_owned())
+    );
+    assert_eq!(
+        rectangle.required,
+        Some(vec![
+            "origin".to_owned(),
+            "width".to_owned(),
+            "height".to_owned()
+        ])
+    );
+
+    let labeled = &one_of[5].properties.as_ref().unwrap()["Labeled"];
+    let properties = labeled.properties.as_ref().unwrap();
+    assert_eq!(properties.keys().collect::<Vec<_>>(), vec!["labelText"]);
+
+    // a circle must not be accepted as a point
+    let circle = serde_json::json!({ "Point": 1.5 });
+    assert!(!is_valid(&spec, shape, &circle));
+}
+
+/// An event
+#[derive(OpenapiSchema, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[allow(dead_code)]
+enum Event {
+    /// Server started
+    Started,
+    Moved(Point),
+    #[serde(rename_all = "camelCase")]
+    Stopped {
+        exit_code: i32,
+        reason: Option<String>,
+    },
+}
+
+#[test]
+fn test_internally_tagged() {
+    assert_variants(&[
+        Event::Started,
+        Event::Moved(Point { x: 1.0, y: 2.0 }),
+        Event::Stopped {
+            exit_code: 1,
+            reason: Some(String::from("killed")),
+        },
+    ]);
+
+    let mut spec = Spec::default();
+    Event::generate_schema(&mut spec);
+    let event = resolve(&spec, "#/components/schemas/Event");
+    assert_eq!(event.description, Some("An event".to_owned()));
+    assert_eq!(
+        event.one_of,
+        Some(vec![
+            ObjectOrReference::Ref {
+                ref_path: "#/components/schemas/Event_Started".to_owned()
+            },
+            ObjectOrReference::Ref {
+                ref_path: "#/components/schemas/Event_Moved".to_owned()
+            },
+            ObjectOrReference::Ref {
+                ref_path: "#/components/schemas/Event_Stopped".to_owned()
+            },
+        ])
+    );
+
+    let discriminator = event.discriminator.as_ref().unwrap();
+    assert_eq!(discriminator.property_name, "kind");
+    let mapping = discriminator.mapping.as_ref().unwrap();
+    assert_eq!(mapping["started"], "#/components/schemas/Event_Started");
+    assert_eq!(mapping["moved"], "#/components/schemas/Event_Moved");
+    assert_eq!(mapping["stopped"], "#/components/schemas/Event_Stopped");
+
+    let started = resolve(&spec, "#/components/schemas/Event_Started");
+    assert_eq!(started.description, Some("Server started".to_owned()));
+    assert_eq!(started.required, Some(vec!["kind".to_owned()]));
+
+    let stopped = resolve(&spec, "#/components/schemas/Event_Stopped");
+    assert_eq!(
+        stopped.required,
+        Some(vec!["kind".to_owned(), "exitCode".to_owned()])
+    );
+    let kind = &stopped.properties.as_ref().unwrap()["kind"];
+    assert_eq!(kind.enum_values, Some(vec!["stopped".to_owned()]));
+
+    // the tag has to match the variant
+    let value = serde_json::json!({ "kind": "started", "exitCode": 0 });
+    assert!(!is_valid(&spec, stopped, &value));
+}
+
+#[derive(OpenapiSchema, Serialize)]
+#[serde(tag = "t", content = "c")]
+#[allow(dead_code)]
+enum Message {
+    Ping,
+    /// Plain text
+    Text(String),
+    Move(Point),
+    Pair(u32, String),
+    Resize {
+        width: u32,
+        height: u32,
+    },
+}
+
+#[test]
+fn test_adjacently_tagged() {
+    assert_variants(&[
+        Message::Ping,
+        Message::Text(String::from("hello")),
+        Message::Move(Point { x: 1.0, y: 2.0 }),
+        Message::Pair(1, String::from("one")),
+        Message::Resize {
+            width: 1,
+            height: 2,
+        },
+    ]);
+
+    let mut spec = Spec::default();
+    Message::generate_schema(&mut spec);
+    let message = resolve(&spec, "#/components/schemas/Message");
+    assert_eq!(message.title, None);
+    assert_eq!(message.description, None);
+
+    let one_of: Vec<_> = message
+        .one_of
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|variant| match variant {
+            ObjectOrReference::Object(ref variant) => variant,
+            _ => panic!("unexpected reference"),
+        })
+        .collect();
+    assert_eq!(one_of.len(), 5);
+
+    let ping = one_of[0];
+    assert_eq!(ping.required, Some(vec!["t".to_owned()]));
+    let properties = ping.properties.as_ref().unwrap();
+    assert_eq!(properties.keys().collect::<Vec<_>>(), vec!["t"]);
+    assert_eq!(properties["t"].enum_values, Some(vec!["Ping".to_owned()]));
+
+    let text = one_of[1];
+    assert_eq!(text.description, Some("Plain text".to_owned()));
+    assert_eq!(text.required, Some(vec!["t".to_owned(), "c".to_owned()]));
+    let properties = text.properties.as_ref().unwrap();
+    assert_eq!(properties["t"].enum_values, Some(vec!["Text".to_owned()]));
+    assert_eq!(properties["c"].schema_type, Some("string".to_owned()));
+
+    let value = serde_json::json!({ "t": "Text", "c": 1 });
+    assert!(!is_valid(&spec, message, &value));
+}
+
+/// Either an id or the full object
+#[derive(OpenapiSchema, Serialize)]
+#[serde(untagged)]
+#[allow(dead_code)]
+enum PointRef {
+    Id(u64),
+    Full(Point),
+    Pair(f64, f64),
+    /// Named point
+    Named {
+        name: String,
+    },
+    Missing,
+}
+
+#[test]
+fn test_untagged() {
+    assert_variants(&[
+        PointRef::Id(1),
+        PointRef::Full(Point { x: 1.0, y: 2.0 }),
+        PointRef::Pair(1.0, 2.0),
+        PointRef::Named {
+            name: String::from("origin"),
+        },
+        PointRef::Missing,
+    ]);
+
+    let mut spec = Spec::default();
+    PointRef::generate_schema(&mut spec);
+    let point_ref = resolve(&spec, "#/components/schemas/PointRef");
+    assert_eq!(
+        point_ref.description,
+        Some("Either an id or the full object".to_owned())
+    );
+    assert_eq!(point_ref.nullable, None);
+
+    let any_of = point_ref.any_of.as_ref().unwrap();
+    assert_eq!(any_of.len(), 5);
+    match any_of[0] {
+        ObjectOrReference::Object(ref id) => {
+            assert_eq!(id.schema_type, Some("integer".to_owned()))
+        }
+        _ => panic!("unexpected reference"),
+    }
+    assert_eq!(
+        any_of[1],
+        ObjectOrReference::Ref {
+            ref_path: "#/components/schemas/Point".to_owned()
+        }
+    );
+    match any_of[3] {
+        ObjectOrReference::Object(ref named) => {
+            assert_eq!(named.description, Some("Named point".to_owned()))
+        }
+        _ => panic!("unexpected reference"),
+    }
+
+    // the unit variant is `null`, which is no object
+    let null = match any_of[4] {
+        ObjectOrReference::Object(ref null) => null,
+        _ => panic!("unexpected reference"),
+    };
+    assert!(is_valid(&spec, null, &serde_json::Value::Null));
+    assert!(!is_valid(&spec, null, &serde_json::json!({})));
+    assert!(!is_valid(&spec, null, &serde_json::json!(1)));
+
+    assert!(!is_valid(&spec, point_ref, &serde_json::json!("origin")));
+}
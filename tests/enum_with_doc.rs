@@ -83,7 +83,7 @@ fn test_enum_without_doc() {
 }
 
 #[test]
-fn test_enum_fail() {
+fn test_enum_with_data() {
     #[derive(OpenapiSchema)]
     #[allow(dead_code)]
     pub enum Status {
@@ -102,21 +102,31 @@ fn test_enum_fail() {
         _ => panic!("unexpected reference"),
     };
 
-    assert_eq!(
-        status.description,
-        Some(
-            r#"
-* Sold: Already sold"#
-                .to_owned()
-        )
-    );
-    assert_eq!(status.schema_type, Some("string".to_owned()));
-    assert_eq!(
-        status.enum_values,
-        Some(vec![
-            "Available".to_owned(),
-            "Pending".to_owned(),
-            "Sold".to_owned()
-        ])
-    );
+    assert_eq!(status.schema_type, None);
+    assert_eq!(status.enum_values, None);
+
+    let one_of: Vec<_> = status
+        .one_of
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|variant| match variant {
+            ObjectOrReference::Object(ref variant) => variant,
+            _ => panic!("unexpected reference"),
+        })
+        .collect();
+    assert_eq!(one_of.len(), 3);
+
+    assert_eq!(one_of[0].schema_type, Some("object".to_owned()));
+    assert_eq!(one_of[0].required, Some(vec!["Available".to_owned()]));
+    let available = &one_of[0].properties.as_ref().unwrap()["Available"];
+    assert_eq!(available.schema_type, Some("integer".to_owned()));
+
+    assert_eq!(one_of[1].description, None);
+    assert_eq!(one_of[1].schema_type, Some("string".to_owned()));
+    assert_eq!(one_of[1].enum_values, Some(vec!["Pending".to_owned()]));
+
+    assert_eq!(one_of[2].description, Some("Already sold".to_owned()));
+    assert_eq!(one_of[2].schema_type, Some("string".to_owned()));
+    assert_eq!(one_of[2].enum_values, Some(vec!["Sold".to_owned()]));
 }
@@ -0,0 +1,194 @@
+use openapi::v3_0::{ObjectOrReference, Spec};
+use openapi_schema::OpenapiSchema;
+use serde::Serialize;
+
+mod common;
+
+use common::component;
+
+#[derive(OpenapiSchema, Serialize)]
+struct User {
+    name: String,
+}
+
+#[derive(OpenapiSchema, Serialize)]
+struct Tag {
+    label: String,
+}
+
+/// A page of items
+#[derive(OpenapiSchema, Serialize)]
+struct Page<T> {
+    items: Vec<T>,
+    total: u64,
+}
+
+#[derive(OpenapiSchema, Serialize)]
+#[openapi(rename = "{T}List")]
+struct List<T> {
+    items: Vec<T>,
+}
+
+#[derive(OpenapiSchema, Serialize)]
+#[allow(dead_code)]
+enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+#[test]
+fn test_generic_struct() {
+    let mut spec = Spec::default();
+    let users = Page::<User>::generate_schema(&mut spec);
+    let tags = Page::<Tag>::generate_schema(&mut spec);
+    Page::<Vec<User>>::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    assert_eq!(
+        users,
+        ObjectOrReference::Ref {
+            ref_path: "#/components/schemas/Page_User".to_owned()
+        }
+    );
+    assert_eq!(
+        tags,
+        ObjectOrReference::Ref {
+            ref_path: "#/components/schemas/Page_Tag".to_owned()
+        }
+    );
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    assert_eq!(
+        schemas.keys().collect::<Vec<_>>(),
+        vec!["Page_Tag", "Page_User", "Page_Vec_User", "Tag", "User"]
+    );
+
+    let page = component(&spec, "Page_User");
+    assert_eq!(page.description, Some("A page of items".to_owned()));
+    let items = &page.properties.as_ref().unwrap()["items"];
+    assert_eq!(
+        items.items.as_ref().unwrap().ref_path,
+        Some("#/components/schemas/User".to_owned())
+    );
+}
+
+#[test]
+fn test_generic_rename() {
+    let mut spec = Spec::default();
+    List::<User>::generate_schema(&mut spec);
+    List::<Page<Tag>>::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    component(&spec, "UserList");
+    component(&spec, "Page_TagList");
+}
+
+#[test]
+fn test_generic_enum() {
+    let mut spec = Spec::default();
+    Either::<User, u64>::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let either = component(&spec, "Either_User_u64");
+    assert_eq!(either.one_of.as_ref().unwrap().len(), 2);
+}
+
+#[test]
+fn test_generic_flatten() {
+    #[derive(OpenapiSchema, Serialize)]
+    struct Response {
+        status: u16,
+        #[serde(flatten)]
+        page: Page<User>,
+    }
+
+    let mut spec = Spec::default();
+    Response::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    assert!(!schemas.contains_key("Page_User"));
+
+    let response = component(&spec, "Response");
+    let properties = response.properties.as_ref().unwrap();
+    assert_eq!(
+        properties.keys().collect::<Vec<_>>(),
+        vec!["items", "status", "total"]
+    );
+}
+
+/// Type without schema, which is only used as marker.
+#[derive(Serialize)]
+struct NoSchema;
+
+#[derive(OpenapiSchema, Serialize)]
+struct Id<T> {
+    value: u64,
+    marker: std::marker::PhantomData<T>,
+}
+
+#[derive(OpenapiSchema, Serialize)]
+#[allow(dead_code)]
+struct Cached<T, C> {
+    value: T,
+    #[serde(skip)]
+    cache: Option<C>,
+}
+
+#[derive(OpenapiSchema, Serialize)]
+#[allow(dead_code)]
+enum Lookup<T, C> {
+    Found(T),
+    #[serde(skip)]
+    Pending(C),
+}
+
+#[test]
+fn test_generic_without_bound() {
+    let mut spec = Spec::default();
+    Id::<NoSchema>::generate_schema(&mut spec);
+    Cached::<u32, NoSchema>::generate_schema(&mut spec);
+    Lookup::<User, NoSchema>::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    assert_eq!(
+        schemas.keys().collect::<Vec<_>>(),
+        vec![
+            "Cached_u32_NoSchema",
+            "Id_NoSchema",
+            "Lookup_User_NoSchema",
+            "User"
+        ]
+    );
+    let cached = component(&spec, "Cached_u32_NoSchema");
+    assert_eq!(
+        cached
+            .properties
+            .as_ref()
+            .unwrap()
+            .keys()
+            .collect::<Vec<_>>(),
+        vec!["value"]
+    );
+}
+
+#[test]
+fn test_container_names() {
+    assert_eq!(Vec::<List<User>>::schema_name(), "Vec_UserList");
+    assert_eq!(Option::<List<User>>::schema_name(), "Option_UserList");
+    assert_eq!(<[List<User>; 2]>::schema_name(), "UserList_2");
+    assert_eq!(<(User, List<Tag>)>::schema_name(), "User_TagList");
+    assert_eq!(
+        std::collections::BTreeMap::<String, List<User>>::schema_name(),
+        "BTreeMap_String_UserList"
+    );
+    assert_eq!(
+        std::collections::HashSet::<List<User>>::schema_name(),
+        "HashSet_UserList"
+    );
+
+    let mut spec = Spec::default();
+    Page::<Vec<List<User>>>::generate_schema(&mut spec);
+    component(&spec, "Page_Vec_UserList");
+}
@@ -168,3 +168,637 @@ fn test_btreemap() {
         })))
     );
 }
+
+#[test]
+fn test_rename() {
+    #[derive(OpenapiSchema, Serialize)]
+    struct A {
+        #[serde(rename = "type")]
+        kind: String,
+        #[serde(rename(serialize = "userId", deserialize = "user_id"))]
+        user_id: u64,
+        optional_field: Option<String>,
+    }
+
+    let mut spec = Spec::default();
+    A::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    let a = match schemas.get("A") {
+        Some(ObjectOrReference::Object(ref a)) => a,
+        _ => panic!("unexpected reference"),
+    };
+
+    let properties = a.properties.as_ref().unwrap();
+    assert!(properties.contains_key("type"));
+    assert!(properties.contains_key("userId"));
+    assert!(properties.contains_key("optional_field"));
+    assert!(!properties.contains_key("kind"));
+    assert!(!properties.contains_key("user_id"));
+
+    assert_eq!(
+        a.required,
+        Some(vec![String::from("type"), String::from("userId")])
+    );
+}
+
+#[test]
+fn test_default() {
+    fn default_name() -> String {
+        String::from("unnamed")
+    }
+
+    #[derive(OpenapiSchema, Serialize, Default)]
+    #[allow(dead_code)]
+    enum Status {
+        Active,
+        #[default]
+        Inactive,
+    }
+
+    // does not implement `Serialize`, so no default value is emitted
+    #[derive(OpenapiSchema, Default)]
+    struct Opaque {
+        #[allow(dead_code)]
+        value: u64,
+    }
+
+    #[derive(OpenapiSchema, Serialize)]
+    #[allow(dead_code)]
+    struct A {
+        id: u64,
+        #[serde(default)]
+        flag: bool,
+        #[serde(default = "default_name")]
+        name: String,
+        #[serde(default)]
+        status: Status,
+        #[serde(default, skip_serializing)]
+        opaque: Opaque,
+    }
+
+    #[derive(OpenapiSchema, Serialize)]
+    #[serde(default)]
+    struct B {
+        count: u32,
+        label: String,
+    }
+
+    impl Default for B {
+        fn default() -> Self {
+            B {
+                count: 42,
+                label: String::from("label"),
+            }
+        }
+    }
+
+    let mut spec = Spec::default();
+    A::generate_schema(&mut spec);
+    B::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    let a = match schemas.get("A") {
+        Some(ObjectOrReference::Object(ref a)) => a,
+        _ => panic!("unexpected reference"),
+    };
+
+    assert_eq!(a.required, Some(vec![String::from("id")]));
+    let properties = a.properties.as_ref().unwrap();
+    assert_eq!(properties["id"].default, None);
+    assert_eq!(properties["flag"].default, Some(serde_json::json!(false)));
+    assert_eq!(
+        properties["name"].default,
+        Some(serde_json::json!("unnamed"))
+    );
+    assert_eq!(
+        properties["status"],
+        Schema {
+            all_of: Some(vec![ObjectOrReference::Ref {
+                ref_path: "#/components/schemas/Status".to_owned()
+            }]),
+            default: Some(serde_json::json!("Inactive")),
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        properties["opaque"],
+        Schema {
+            all_of: Some(vec![ObjectOrReference::Ref {
+                ref_path: "#/components/schemas/Opaque".to_owned()
+            }]),
+            write_only: Some(true),
+            ..Default::default()
+        }
+    );
+
+    let b = match schemas.get("B") {
+        Some(ObjectOrReference::Object(ref b)) => b,
+        _ => panic!("unexpected reference"),
+    };
+
+    assert_eq!(b.required, None);
+    let properties = b.properties.as_ref().unwrap();
+    assert_eq!(properties["count"].default, Some(serde_json::json!(42)));
+    assert_eq!(
+        properties["label"].default,
+        Some(serde_json::json!("label"))
+    );
+}
+
+#[test]
+fn test_skip() {
+    // does not implement `OpenapiSchema`
+    #[derive(Default)]
+    struct Cache;
+
+    #[derive(OpenapiSchema, Serialize)]
+    #[allow(dead_code)]
+    struct A {
+        id: u64,
+        #[serde(skip)]
+        cache: Cache,
+        #[serde(skip_serializing, skip_deserializing)]
+        handle: Cache,
+        #[serde(skip_deserializing)]
+        created_at: String,
+        #[serde(skip_serializing)]
+        password: String,
+    }
+
+    let mut spec = Spec::default();
+    A::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    let a = match schemas.get("A") {
+        Some(ObjectOrReference::Object(ref a)) => a,
+        _ => panic!("unexpected reference"),
+    };
+
+    let properties = a.properties.as_ref().unwrap();
+    assert_eq!(
+        properties.keys().collect::<Vec<_>>(),
+        vec!["created_at", "id", "password"]
+    );
+    assert_eq!(properties["id"].read_only, None);
+    assert_eq!(properties["id"].write_only, None);
+    assert_eq!(properties["created_at"].read_only, Some(true));
+    assert_eq!(properties["created_at"].write_only, None);
+    assert_eq!(properties["password"].read_only, None);
+    assert_eq!(properties["password"].write_only, Some(true));
+}
+
+#[test]
+fn test_newtype_struct() {
+    /// Id of a user
+    #[derive(OpenapiSchema, Serialize)]
+    struct UserId(u64);
+
+    /// Id of an order
+    #[derive(OpenapiSchema, Serialize)]
+    #[openapi(inline)]
+    struct OrderId(u64);
+
+    #[derive(OpenapiSchema, Serialize)]
+    struct Wrapper(A);
+
+    #[derive(OpenapiSchema, Serialize)]
+    struct A {
+        user_id: UserId,
+        order_id: OrderId,
+    }
+
+    let mut spec = Spec::default();
+    Wrapper::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    assert!(!schemas.contains_key("OrderId"));
+
+    let user_id = match schemas.get("UserId") {
+        Some(ObjectOrReference::Object(ref user_id)) => user_id,
+        _ => panic!("unexpected reference"),
+    };
+    assert_eq!(user_id.description, Some("Id of a user".to_owned()));
+    assert_eq!(user_id.schema_type, Some("integer".to_owned()));
+    assert_eq!(user_id.format, Some("int64".to_owned()));
+
+    let a = match schemas.get("A") {
+        Some(ObjectOrReference::Object(ref a)) => a,
+        _ => panic!("unexpected reference"),
+    };
+    let properties = a.properties.as_ref().unwrap();
+    assert_eq!(
+        properties["user_id"].ref_path,
+        Some("#/components/schemas/UserId".to_owned())
+    );
+    let order_id = &properties["order_id"];
+    assert_eq!(order_id.description, Some("Id of an order".to_owned()));
+    assert_eq!(order_id.schema_type, Some("integer".to_owned()));
+
+    let wrapper = match schemas.get("Wrapper") {
+        Some(ObjectOrReference::Object(ref wrapper)) => wrapper,
+        _ => panic!("unexpected reference"),
+    };
+    assert_eq!(wrapper.ref_path, Some("#/components/schemas/A".to_owned()));
+}
+
+#[test]
+fn test_tuple_struct() {
+    #[derive(OpenapiSchema, Serialize)]
+    struct Point(f64, f64);
+
+    #[derive(OpenapiSchema, Serialize)]
+    struct Entry(String, u32, Point);
+
+    let mut spec = Spec::default();
+    Entry::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    let point = match schemas.get("Point") {
+        Some(ObjectOrReference::Object(ref point)) => point,
+        _ => panic!("unexpected reference"),
+    };
+    assert_eq!(point.schema_type, Some("array".to_owned()));
+    assert_eq!(point.min_items, Some(2));
+    assert_eq!(point.max_items, Some(2));
+    assert_eq!(
+        point.items.as_ref().unwrap().schema_type,
+        Some("number".to_owned())
+    );
+
+    let entry = match schemas.get("Entry") {
+        Some(ObjectOrReference::Object(ref entry)) => entry,
+        _ => panic!("unexpected reference"),
+    };
+    assert_eq!(entry.schema_type, Some("array".to_owned()));
+    assert_eq!(entry.min_items, Some(3));
+    assert_eq!(entry.max_items, Some(3));
+    let items = entry.items.as_ref().unwrap().any_of.as_ref().unwrap();
+    assert_eq!(items.len(), 3);
+    assert_eq!(
+        items[2],
+        ObjectOrReference::Ref {
+            ref_path: "#/components/schemas/Point".to_owned()
+        }
+    );
+    // the union of the element schemas loses the order, which is given by the extension
+    assert_eq!(
+        entry.extensions.get("x-prefix-items"),
+        Some(&serde_json::json!([
+            {"type": "string"},
+            {"type": "integer", "format": "int64", "minimum": 0, "maximum": u32::MAX},
+            {"$ref": "#/components/schemas/Point"},
+        ]))
+    );
+}
+
+#[test]
+fn test_tuple_items_policy() {
+    use openapi_schema::{Generator, TupleItemsPolicy};
+
+    #[derive(OpenapiSchema, Serialize)]
+    struct Range(u8, String);
+
+    let mut generator = Generator::new().tuple_items_policy(TupleItemsPolicy::PrefixItems);
+    generator.generate::<Range>();
+    let spec = generator.into_spec();
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    let range = match schemas.get("Range") {
+        Some(ObjectOrReference::Object(ref range)) => range,
+        _ => panic!("unexpected reference"),
+    };
+    let prefix_items = range.extensions["prefixItems"].as_array().unwrap();
+    assert_eq!(prefix_items[0]["type"], "integer");
+    assert_eq!(prefix_items[1]["type"], "string");
+    assert_eq!(range.extensions.get("x-prefix-items"), None);
+
+    let mut generator = Generator::new().tuple_items_policy(TupleItemsPolicy::Ignore);
+    generator.generate::<Range>();
+    let spec = generator.into_spec();
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    match schemas.get("Range") {
+        Some(ObjectOrReference::Object(ref range)) => assert!(range.extensions.is_empty()),
+        _ => panic!("unexpected reference"),
+    }
+}
+
+#[test]
+fn test_tuple_skip() {
+    use std::marker::PhantomData;
+
+    #[derive(OpenapiSchema, Serialize)]
+    struct Tagged<T>(u32, #[serde(skip)] PhantomData<T>);
+
+    #[derive(OpenapiSchema, Serialize)]
+    enum Shape {
+        Line(f64, #[serde(skip)] String, f64),
+        Hidden(#[serde(skip)] String),
+    }
+
+    let tagged = Tagged::<String>(1, PhantomData);
+    assert_eq!(
+        serde_json::to_value(&tagged).unwrap(),
+        serde_json::json!([1])
+    );
+    let line = Shape::Line(1.0, String::from("dashed"), 2.0);
+    assert_eq!(
+        serde_json::to_value(&line).unwrap(),
+        serde_json::json!({ "Line": [1.0, 2.0] })
+    );
+    let hidden = Shape::Hidden(String::from("secret"));
+    assert_eq!(serde_json::to_value(&hidden).unwrap(), "Hidden");
+
+    let mut spec = Spec::default();
+    Tagged::<String>::generate_schema(&mut spec);
+    Shape::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    let tagged = match schemas.get("Tagged_String") {
+        Some(ObjectOrReference::Object(ref tagged)) => tagged,
+        _ => panic!("unexpected reference"),
+    };
+    assert_eq!(tagged.min_items, Some(1));
+    assert_eq!(tagged.max_items, Some(1));
+    assert_eq!(
+        tagged.items.as_ref().unwrap().schema_type,
+        Some("integer".to_owned())
+    );
+
+    let shape = match schemas.get("Shape") {
+        Some(ObjectOrReference::Object(ref shape)) => shape,
+        _ => panic!("unexpected reference"),
+    };
+    let one_of: Vec<_> = shape
+        .one_of
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|variant| match variant {
+            ObjectOrReference::Object(ref variant) => variant,
+            _ => panic!("unexpected reference"),
+        })
+        .collect();
+    let line = &one_of[0].properties.as_ref().unwrap()["Line"];
+    assert_eq!(line.min_items, Some(2));
+    assert_eq!(line.max_items, Some(2));
+    assert_eq!(
+        line.items.as_ref().unwrap().schema_type,
+        Some("number".to_owned())
+    );
+    assert_eq!(one_of[1].enum_values, Some(vec!["Hidden".to_owned()]));
+}
+
+#[test]
+fn test_unit_struct() {
+    /// Nothing
+    #[derive(OpenapiSchema, Serialize)]
+    struct Unit;
+
+    let mut spec = Spec::default();
+    Unit::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    assert_eq!(
+        schemas.get("Unit"),
+        Some(&ObjectOrReference::Object(Schema {
+            description: Some("Nothing".to_owned()),
+            schema_type: Some("object".to_owned()),
+            nullable: Some(true),
+            not: Some(Box::new(Schema {
+                schema_type: Some("object".to_owned()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }))
+    );
+    assert_eq!(
+        serde_json::to_value(&Unit).unwrap(),
+        serde_json::Value::Null
+    );
+}
+
+#[test]
+fn test_transparent() {
+    /// Email address
+    #[derive(OpenapiSchema, Serialize)]
+    #[serde(transparent)]
+    struct Email(String);
+
+    #[derive(OpenapiSchema, Serialize)]
+    #[serde(transparent)]
+    struct Slug(String);
+
+    #[derive(OpenapiSchema, Serialize)]
+    struct Currency {
+        code: String,
+    }
+
+    /// Amount of money
+    #[derive(OpenapiSchema, Serialize)]
+    #[serde(transparent)]
+    struct Money {
+        cents: u64,
+        #[serde(skip)]
+        #[allow(dead_code)]
+        currency: std::marker::PhantomData<Currency>,
+    }
+
+    #[derive(OpenapiSchema, Serialize)]
+    #[serde(transparent)]
+    struct CurrencyRef(Currency);
+
+    /// Currency of the price
+    #[derive(OpenapiSchema, Serialize)]
+    #[serde(transparent)]
+    struct PriceCurrency(Currency);
+
+    #[derive(OpenapiSchema, Serialize)]
+    struct A {
+        email: Email,
+        slug: Slug,
+        money: Money,
+        currency: CurrencyRef,
+        price_currency: PriceCurrency,
+    }
+
+    let mut spec = Spec::default();
+    A::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    assert_eq!(schemas.keys().collect::<Vec<_>>(), vec!["A", "Currency"]);
+
+    let a = match schemas.get("A") {
+        Some(ObjectOrReference::Object(ref a)) => a,
+        _ => panic!("unexpected reference"),
+    };
+    let properties = a.properties.as_ref().unwrap();
+    assert_eq!(
+        properties["email"],
+        Schema {
+            schema_type: Some("string".to_owned()),
+            description: Some("Email address".to_owned()),
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        properties["slug"],
+        Schema {
+            schema_type: Some("string".to_owned()),
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        properties["money"].description,
+        Some("Amount of money".to_owned())
+    );
+    assert_eq!(properties["money"].format, Some("int64".to_owned()));
+    assert_eq!(
+        properties["currency"],
+        Schema {
+            ref_path: Some("#/components/schemas/Currency".to_owned()),
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        properties["price_currency"],
+        Schema {
+            description: Some("Currency of the price".to_owned()),
+            all_of: Some(vec![ObjectOrReference::Ref {
+                ref_path: "#/components/schemas/Currency".to_owned()
+            }]),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_lifetimes() {
+    use std::borrow::Cow;
+
+    #[derive(OpenapiSchema, Serialize)]
+    struct B {
+        val: String,
+    }
+
+    #[derive(OpenapiSchema, Serialize)]
+    struct Request<'a, 'b: 'a> {
+        name: &'a str,
+        tags: &'a [String],
+        description: Cow<'b, str>,
+        values: Cow<'a, [u32]>,
+        inner: &'b B,
+        optional: Option<&'a str>,
+    }
+
+    let mut spec = Spec::default();
+    Request::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    let request = match schemas.get("Request") {
+        Some(ObjectOrReference::Object(ref request)) => request,
+        _ => panic!("unexpected reference"),
+    };
+
+    let properties = request.properties.as_ref().unwrap();
+    assert_eq!(properties["name"].schema_type, Some("string".to_owned()));
+    assert_eq!(properties["tags"].schema_type, Some("array".to_owned()));
+    assert_eq!(
+        properties["description"].schema_type,
+        Some("string".to_owned())
+    );
+    assert_eq!(properties["values"].schema_type, Some("array".to_owned()));
+    assert_eq!(
+        properties["inner"].ref_path,
+        Some("#/components/schemas/B".to_owned())
+    );
+    assert_eq!(
+        request.required,
+        Some(vec![
+            "name".to_owned(),
+            "tags".to_owned(),
+            "description".to_owned(),
+            "values".to_owned(),
+            "inner".to_owned(),
+        ])
+    );
+}
+
+#[test]
+#[allow(unused_parens)]
+fn test_non_path_types() {
+    #[derive(OpenapiSchema, Serialize)]
+    struct B {
+        val: String,
+    }
+
+    #[derive(OpenapiSchema, Serialize)]
+    struct A<'a> {
+        hash: [u32; 4],
+        point: (f64, f64),
+        pair: (String, B),
+        reference: &'a B,
+        parens: (Option<u32>),
+    }
+
+    let mut spec = Spec::default();
+    A::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    let a = match schemas.get("A") {
+        Some(ObjectOrReference::Object(ref a)) => a,
+        _ => panic!("unexpected reference"),
+    };
+
+    let properties = a.properties.as_ref().unwrap();
+
+    let hash = &properties["hash"];
+    assert_eq!(hash.schema_type, Some("array".to_owned()));
+    assert_eq!(hash.min_items, Some(4));
+    assert_eq!(hash.max_items, Some(4));
+    let hash_items = hash.items.as_ref().unwrap();
+    assert_eq!(hash_items.schema_type, Some("integer".to_owned()));
+
+    let point = &properties["point"];
+    assert_eq!(point.schema_type, Some("array".to_owned()));
+    assert_eq!(point.min_items, Some(2));
+    assert_eq!(point.max_items, Some(2));
+    let point_items = point.items.as_ref().unwrap();
+    assert_eq!(point_items.schema_type, Some("number".to_owned()));
+
+    let pair = &properties["pair"];
+    assert_eq!(pair.min_items, Some(2));
+    let pair_items = pair.items.as_ref().unwrap();
+    assert_eq!(pair_items.any_of.as_ref().map(Vec::len), Some(2));
+    assert_eq!(
+        pair.extensions.get("x-prefix-items"),
+        Some(&serde_json::json!([
+            {"type": "string"},
+            {"$ref": "#/components/schemas/B"},
+        ]))
+    );
+
+    assert_eq!(
+        properties["reference"].ref_path,
+        Some("#/components/schemas/B".to_owned())
+    );
+
+    assert_eq!(
+        a.required,
+        Some(vec![
+            "hash".to_owned(),
+            "point".to_owned(),
+            "pair".to_owned(),
+            "reference".to_owned(),
+        ])
+    );
+}
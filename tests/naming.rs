@@ -0,0 +1,233 @@
+use openapi::v3_0::{ObjectOrReference, Spec};
+use openapi_schema::{Generator, NamingStrategy, OpenapiSchema};
+use serde::Serialize;
+
+mod v1 {
+    use openapi_schema::OpenapiSchema;
+    use serde::Serialize;
+
+    #[derive(OpenapiSchema, Serialize)]
+    pub struct User {
+        pub name: String,
+    }
+}
+
+mod v2 {
+    use openapi_schema::OpenapiSchema;
+    use serde::Serialize;
+
+    #[derive(OpenapiSchema, Serialize)]
+    pub struct User {
+        pub first_name: String,
+        pub last_name: String,
+    }
+}
+
+#[derive(OpenapiSchema, Serialize)]
+struct Users {
+    old: v1::User,
+    new: v2::User,
+}
+
+#[derive(OpenapiSchema, Serialize)]
+struct Page<T> {
+    items: Vec<T>,
+}
+
+mod generic {
+    use openapi_schema::OpenapiSchema;
+    use serde::Serialize;
+
+    #[derive(OpenapiSchema, Serialize)]
+    pub struct Started {
+        pub at: u64,
+    }
+
+    #[derive(OpenapiSchema, Serialize)]
+    pub struct Event<T> {
+        pub payload: T,
+    }
+}
+
+#[derive(OpenapiSchema, Serialize)]
+#[serde(tag = "kind")]
+#[allow(dead_code)]
+enum Event {
+    Started,
+    Stopped,
+}
+
+#[derive(OpenapiSchema, Serialize)]
+struct Log {
+    tagged: Event,
+    generic: generic::Event<generic::Started>,
+}
+
+#[test]
+#[should_panic(
+    expected = "component name `User` of `naming::v2::User` is already used by `naming::v1::User`"
+)]
+fn test_clash_error() {
+    let mut spec = Spec::default();
+    Users::generate_schema(&mut spec);
+}
+
+#[test]
+#[should_panic(
+    expected = "component name `User` of `naming::v2::User` is already used by `naming::v1::User`"
+)]
+fn test_clash_error_subsequent_generation() {
+    let mut generator = Generator::new();
+    generator.generate::<v1::User>();
+    // the component names move with the generator
+    let mut generator = Box::new(generator);
+    generator.generate::<v2::User>();
+}
+
+#[test]
+fn test_separate_specs() {
+    let mut first = Generator::new();
+    first.generate::<v1::User>();
+    let mut second = Generator::new();
+    second.generate::<v2::User>();
+    first.generate::<v1::User>();
+    let mut third = Spec::default();
+    v1::User::generate_schema(&mut third);
+    let mut fourth = Spec::default();
+    v2::User::generate_schema(&mut fourth);
+
+    for (spec, field) in &[
+        (first.into_spec(), "name"),
+        (second.into_spec(), "first_name"),
+        (third, "name"),
+        (fourth, "first_name"),
+    ] {
+        let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+        assert_eq!(schemas.keys().collect::<Vec<_>>(), vec!["User"]);
+        match schemas.get("User") {
+            Some(ObjectOrReference::Object(user)) => {
+                assert!(user.properties.as_ref().unwrap().contains_key(*field))
+            }
+            _ => panic!("missing User"),
+        }
+    }
+}
+
+#[test]
+fn test_no_clash_detection_between_calls() {
+    // separate calls of `generate_schema` do not share the component names, hence `v2::User`
+    // refers to the component of `v1::User`
+    let mut spec = Spec::default();
+    v1::User::generate_schema(&mut spec);
+    let reference = v2::User::generate_schema(&mut spec);
+    assert_eq!(
+        reference,
+        ObjectOrReference::Ref {
+            ref_path: "#/components/schemas/User".into()
+        }
+    );
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    match schemas.get("User") {
+        Some(ObjectOrReference::Object(user)) => {
+            assert!(user.properties.as_ref().unwrap().contains_key("name"))
+        }
+        _ => panic!("missing User"),
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "component name `Event_Started` of `naming::generic::Event<naming::generic::Started>` \
+                is already used by `naming::Event::Started`"
+)]
+fn test_variant_clash_error() {
+    let mut spec = Spec::default();
+    Log::generate_schema(&mut spec);
+}
+
+#[test]
+fn test_variant_clash_module_path() {
+    let mut generator = Generator::new().naming_strategy(NamingStrategy::ModulePath);
+    generator.generate::<Log>();
+    let spec = generator.into_spec();
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    assert_eq!(
+        schemas.keys().collect::<Vec<_>>(),
+        vec![
+            "Event",
+            "Event_Started",
+            "Event_Stopped",
+            "Log",
+            "Started",
+            "naming_generic_Event_Started"
+        ]
+    );
+    match schemas.get("Event_Started") {
+        Some(ObjectOrReference::Object(started)) => {
+            assert!(started.properties.as_ref().unwrap().contains_key("kind"))
+        }
+        _ => panic!("missing Event_Started"),
+    }
+}
+
+#[test]
+fn test_clash_module_path() {
+    let mut generator = Generator::new().naming_strategy(NamingStrategy::ModulePath);
+    generator.generate::<Users>();
+    // the name is kept on subsequent generations
+    generator.generate::<v2::User>();
+    let spec = generator.into_spec();
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    assert_eq!(
+        schemas.keys().collect::<Vec<_>>(),
+        vec!["User", "Users", "naming_v2_User"]
+    );
+
+    let users = match schemas.get("Users") {
+        Some(ObjectOrReference::Object(users)) => users,
+        _ => panic!("missing Users"),
+    };
+    let properties = users.properties.as_ref().unwrap();
+    assert_eq!(
+        properties["old"].ref_path,
+        Some("#/components/schemas/User".into())
+    );
+    assert_eq!(
+        properties["new"].ref_path,
+        Some("#/components/schemas/naming_v2_User".into())
+    );
+
+    let new_user = match schemas.get("naming_v2_User") {
+        Some(ObjectOrReference::Object(user)) => user,
+        _ => panic!("missing naming_v2_User"),
+    };
+    assert!(new_user
+        .properties
+        .as_ref()
+        .unwrap()
+        .contains_key("first_name"));
+}
+
+#[test]
+fn test_clash_in_type_arguments() {
+    let mut generator = Generator::new().naming_strategy(NamingStrategy::ModulePath);
+    generator.generate::<Page<Vec<v1::User>>>();
+    generator.generate::<Page<Vec<v2::User>>>();
+    let spec = generator.into_spec();
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    assert_eq!(
+        schemas.keys().collect::<Vec<_>>(),
+        vec![
+            "Page_Vec_User",
+            "Page_Vec_naming_v2_User",
+            "User",
+            "naming_v2_User"
+        ]
+    );
+}
@@ -0,0 +1,91 @@
+use openapi::v3_0::{ObjectOrReference, Schema, Spec};
+use openapi_schema::{Generator, OpenapiSchema};
+use serde::Serialize;
+
+#[derive(OpenapiSchema, Serialize)]
+struct User {
+    name: String,
+}
+
+#[derive(OpenapiSchema, Serialize)]
+struct Profile {
+    age: Option<u32>,
+    friend: Option<User>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nickname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    partner: Option<User>,
+    aliases: Vec<Option<String>>,
+}
+
+fn properties(spec: &Spec) -> &std::collections::BTreeMap<String, Schema> {
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    match schemas.get("Profile") {
+        Some(ObjectOrReference::Object(profile)) => profile.properties.as_ref().unwrap(),
+        _ => panic!("missing Profile"),
+    }
+}
+
+#[test]
+fn test_option_not_nullable_by_default() {
+    let mut spec = Spec::default();
+    Profile::generate_schema(&mut spec);
+
+    let properties = properties(&spec);
+    assert_eq!(properties["age"].nullable, None);
+    assert_eq!(
+        properties["friend"].ref_path,
+        Some("#/components/schemas/User".into())
+    );
+}
+
+#[test]
+fn test_nullable_options() {
+    let mut generator = Generator::new().nullable_options(true);
+    generator.generate::<Profile>();
+    let spec = generator.into_spec();
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let properties = properties(&spec);
+    let user_ref = ObjectOrReference::Ref {
+        ref_path: "#/components/schemas/User".into(),
+    };
+
+    let age = &properties["age"];
+    assert_eq!(age.schema_type, Some("integer".into()));
+    assert_eq!(age.nullable, Some(true));
+
+    let friend = &properties["friend"];
+    assert_eq!(friend.ref_path, None);
+    assert_eq!(friend.nullable, Some(true));
+    assert_eq!(friend.all_of, Some(vec![user_ref]));
+
+    // `None` is skipped, hence never serialized as `null`
+    let nickname = &properties["nickname"];
+    assert_eq!(nickname.schema_type, Some("string".into()));
+    assert_eq!(nickname.nullable, None);
+    let partner = &properties["partner"];
+    assert_eq!(partner.ref_path, Some("#/components/schemas/User".into()));
+    assert_eq!(partner.nullable, None);
+
+    let aliases = &properties["aliases"];
+    assert_eq!(aliases.items.as_ref().unwrap().nullable, Some(true));
+
+    let profile = serde_json::to_value(Profile {
+        age: None,
+        friend: None,
+        nickname: None,
+        partner: None,
+        aliases: vec![None],
+    })
+    .unwrap();
+    assert_eq!(
+        profile,
+        serde_json::json!({"age": null, "friend": null, "aliases": [null]})
+    );
+
+    // the settings apply only to the generator
+    let mut spec = Spec::default();
+    Profile::generate_schema(&mut spec);
+    assert_eq!(self::properties(&spec)["age"].nullable, None);
+}
@@ -0,0 +1,261 @@
+use openapi::v3_0::{ObjectOrReference, Spec};
+use openapi_schema::OpenapiSchema;
+use serde::Serialize;
+use serde_json::json;
+
+mod common;
+
+use common::component;
+
+/// Doc title
+///
+/// Doc description
+#[derive(OpenapiSchema, Serialize)]
+#[openapi(
+    rename = "Account",
+    title = "User account",
+    description = "An account of a user",
+    example = "{}",
+    deprecated
+)]
+struct User {
+    /// The id
+    #[openapi(
+        rename = "userId",
+        format = "uuid",
+        description = "Unique id",
+        example = "0b5fcd9b-0ad0-4d29-8a0c-4a2f1a1f3e4f",
+        read_only
+    )]
+    #[serde(rename = "userId")]
+    id: String,
+    #[openapi(nullable, example = 42)]
+    age: Option<u32>,
+    #[openapi(write_only, title = "Password")]
+    password: String,
+    #[openapi(example = 1.5)]
+    score: f64,
+    #[openapi(example = true, deprecated)]
+    active: bool,
+    #[openapi(description = "Primary group", nullable)]
+    group: Group,
+}
+
+#[derive(OpenapiSchema, Serialize)]
+struct Group {
+    name: String,
+}
+
+/// Status of a user
+#[derive(OpenapiSchema, Serialize)]
+#[allow(dead_code)]
+enum Status {
+    /// Still active
+    Active,
+    #[openapi(rename = "gone", description = "Deleted by the user")]
+    Deleted,
+}
+
+#[derive(OpenapiSchema, Serialize)]
+#[openapi(title = "Shape", nullable)]
+#[serde(tag = "type")]
+#[allow(dead_code)]
+enum Shape {
+    #[openapi(title = "Circle", deprecated)]
+    Circle { radius: f64 },
+    #[openapi(rename = "rect")]
+    #[serde(rename = "rect")]
+    Rectangle { width: f64, height: f64 },
+}
+
+#[derive(OpenapiSchema, Serialize)]
+#[allow(dead_code)]
+enum Value {
+    #[openapi(description = "A number", example = 7)]
+    Number(u64),
+    Text(String),
+}
+
+#[derive(OpenapiSchema, Serialize)]
+#[serde(transparent)]
+#[openapi(format = "email", example = "jane@example.com")]
+struct Email(String);
+
+#[test]
+fn test_container_overrides() {
+    let mut spec = Spec::default();
+    User::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let user = component(&spec, "Account");
+    assert_eq!(user.title, Some("User account".into()));
+    assert_eq!(user.description, Some("An account of a user".into()));
+    assert_eq!(user.example, Some(json!("{}")));
+    assert_eq!(user.deprecated, Some(true));
+}
+
+#[test]
+fn test_field_overrides() {
+    let mut spec = Spec::default();
+    User::generate_schema(&mut spec);
+
+    let user = component(&spec, "Account");
+    let properties = user.properties.as_ref().unwrap();
+
+    let id = &properties["userId"];
+    assert_eq!(id.format, Some("uuid".into()));
+    assert_eq!(id.description, Some("Unique id".into()));
+    assert_eq!(
+        id.example,
+        Some(json!("0b5fcd9b-0ad0-4d29-8a0c-4a2f1a1f3e4f"))
+    );
+    assert_eq!(id.read_only, Some(true));
+
+    let age = &properties["age"];
+    assert_eq!(age.nullable, Some(true));
+    assert_eq!(age.example, Some(json!(42)));
+
+    let password = &properties["password"];
+    assert_eq!(password.write_only, Some(true));
+    assert_eq!(password.title, Some("Password".into()));
+
+    assert_eq!(properties["score"].example, Some(json!(1.5)));
+    assert_eq!(properties["active"].example, Some(json!(true)));
+    assert_eq!(properties["active"].deprecated, Some(true));
+
+    // siblings of `$ref` are moved into a wrapper
+    let group = &properties["group"];
+    assert_eq!(group.ref_path, None);
+    assert_eq!(group.description, Some("Primary group".into()));
+    assert_eq!(group.nullable, Some(true));
+    assert_eq!(
+        group.all_of,
+        Some(vec![ObjectOrReference::Ref {
+            ref_path: "#/components/schemas/Group".into()
+        }])
+    );
+
+    let serialized = serde_json::to_value(User {
+        id: "id".into(),
+        age: None,
+        password: "secret".into(),
+        score: 0.0,
+        active: true,
+        group: Group { name: "g".into() },
+    })
+    .unwrap();
+    let mut keys: Vec<_> = serialized.as_object().unwrap().keys().collect();
+    let mut property_names: Vec<_> = properties.keys().collect();
+    keys.sort();
+    property_names.sort();
+    assert_eq!(keys, property_names);
+}
+
+#[test]
+fn test_string_variant_overrides() {
+    let mut spec = Spec::default();
+    Status::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let status = component(&spec, "Status");
+    assert_eq!(
+        status.enum_values,
+        Some(vec!["Active".into(), "gone".into()])
+    );
+    assert_eq!(
+        status.description,
+        Some("Status of a user\n* Active: Still active\n* gone: Deleted by the user".into())
+    );
+}
+
+#[test]
+fn test_variant_overrides() {
+    let mut spec = Spec::default();
+    Shape::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let shape = component(&spec, "Shape");
+    assert_eq!(shape.title, Some("Shape".into()));
+    assert_eq!(shape.nullable, Some(true));
+    let mapping = shape
+        .discriminator
+        .as_ref()
+        .unwrap()
+        .mapping
+        .as_ref()
+        .unwrap();
+    assert!(mapping.contains_key("Circle"));
+    assert!(mapping.contains_key("rect"));
+
+    let circle = component(&spec, "Shape_Circle");
+    assert_eq!(circle.title, Some("Circle".into()));
+    assert_eq!(circle.deprecated, Some(true));
+
+    let mut spec = Spec::default();
+    Value::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let value = component(&spec, "Value");
+    let number = match &value.one_of.as_ref().unwrap()[0] {
+        ObjectOrReference::Object(number) => number,
+        _ => panic!("unexpected reference"),
+    };
+    assert_eq!(number.description, Some("A number".into()));
+    assert_eq!(number.example, Some(json!(7)));
+}
+
+#[test]
+fn test_inline_overrides() {
+    let mut spec = Spec::default();
+    let email = match Email::generate_schema(&mut spec) {
+        ObjectOrReference::Object(email) => email,
+        _ => panic!("unexpected reference"),
+    };
+    assert_eq!(email.schema_type, Some("string".into()));
+    assert_eq!(email.format, Some("email".into()));
+    assert_eq!(email.example, Some(json!("jane@example.com")));
+}
+
+#[derive(OpenapiSchema, Serialize)]
+struct Signup {
+    #[openapi(min_length = 1, max_length = 64, pattern = "^[a-z]+$")]
+    name: String,
+    #[openapi(minimum = 0, maximum = 100, exclusive_maximum, multiple_of = 5)]
+    score: u32,
+    #[openapi(minimum = "-273.15", exclusive_minimum, multiple_of = 0.5)]
+    temperature: f64,
+    #[openapi(min_items = 1, max_items = 10, unique_items)]
+    tags: Vec<String>,
+}
+
+#[test]
+fn test_validation_keywords() {
+    let mut spec = Spec::default();
+    Signup::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let signup = component(&spec, "Signup");
+    let properties = signup.properties.as_ref().unwrap();
+
+    let name = &properties["name"];
+    assert_eq!(name.min_length, Some(1));
+    assert_eq!(name.max_length, Some(64));
+    assert_eq!(name.pattern, Some("^[a-z]+$".into()));
+
+    let score = &properties["score"];
+    assert_eq!(score.minimum, Some(json!(0)));
+    assert_eq!(score.maximum, Some(json!(100)));
+    assert_eq!(score.exclusive_minimum, None);
+    assert_eq!(score.exclusive_maximum, Some(true));
+    assert_eq!(score.multiple_of, Some(json!(5)));
+
+    let temperature = &properties["temperature"];
+    assert_eq!(temperature.minimum, Some(json!(-273.15)));
+    assert_eq!(temperature.exclusive_minimum, Some(true));
+    assert_eq!(temperature.multiple_of, Some(json!(0.5)));
+
+    let tags = &properties["tags"];
+    assert_eq!(tags.min_items, Some(1));
+    assert_eq!(tags.max_items, Some(10));
+    assert_eq!(tags.unique_items, Some(true));
+}
@@ -1,10 +1,11 @@
 use openapi::v3_0::{ObjectOrReference, Spec};
 use openapi_schema::OpenapiSchema;
+use serde::Serialize;
 
 /// A pet for sale in the pet store
-#[derive(OpenapiSchema)]
+#[derive(OpenapiSchema, Serialize)]
 #[allow(dead_code)]
-// #[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
 pub struct Pet {
     id: Option<i64>,
     category: Option<Category>,
@@ -15,7 +16,7 @@ pub struct Pet {
 }
 
 /// Pet status in the store
-#[derive(OpenapiSchema)]
+#[derive(OpenapiSchema, Serialize)]
 #[allow(dead_code)]
 pub enum Status {
     Available,
@@ -24,7 +25,7 @@ pub enum Status {
 }
 
 /// A category for a pet
-#[derive(OpenapiSchema)]
+#[derive(OpenapiSchema, Serialize)]
 #[allow(dead_code)]
 struct Category {
     id: Option<i64>,
@@ -32,7 +33,7 @@ struct Category {
 }
 
 /// A tag for a pet
-#[derive(OpenapiSchema)]
+#[derive(OpenapiSchema, Serialize)]
 #[allow(dead_code)]
 struct Tag {
     id: Option<i64>,
@@ -42,7 +43,7 @@ struct Tag {
 /// An uploaded response
 ///
 /// Describes the result of uploading an image resource
-#[derive(OpenapiSchema)]
+#[derive(OpenapiSchema, Serialize)]
 #[allow(dead_code)]
 pub struct ApiResponse {
     code: Option<i32>,
@@ -53,20 +54,20 @@ pub struct ApiResponse {
 /// Pet Order
 ///
 /// An order for a pets from the pet store
-#[derive(OpenapiSchema)]
+#[derive(OpenapiSchema, Serialize)]
 #[allow(dead_code)]
-// #[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
 pub struct Order {
     id: Option<i64>,
     pet_id: Option<i64>,
     quantity: Option<i32>,
     ship_date: Option<String>,
     status: Option<OrderStatus>,
-    // #[serde(default)]
-    complete: Option<bool>,
+    #[serde(default)]
+    complete: bool,
 }
 
-#[derive(OpenapiSchema)]
+#[derive(OpenapiSchema, Serialize)]
 #[allow(dead_code)]
 enum OrderStatus {
     Placed,
@@ -77,9 +78,9 @@ enum OrderStatus {
 /// a User
 ///
 /// A User who is purchasing from the pet store
-#[derive(OpenapiSchema)]
+#[derive(OpenapiSchema, Serialize)]
 #[allow(dead_code)]
-// #[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
 pub struct User {
     id: Option<i64>,
     username: Option<String>,
@@ -164,7 +165,7 @@ fn test_pet_derive() {
 
     assert_eq!(
         pet.required,
-        Some(vec![String::from("name"), String::from("photo_urls")])
+        Some(vec![String::from("name"), String::from("photoUrls")])
     );
 }
 
@@ -183,6 +184,6 @@ fn test_attr_doc() {
     };
 
     let properties = user.properties.as_ref().unwrap();
-    let property = properties.get("user_status").unwrap();
+    let property = properties.get("userStatus").unwrap();
     assert_eq!(property.description, Some(String::from("User Status")));
 }
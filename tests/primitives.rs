@@ -0,0 +1,162 @@
+use openapi::v3_0::{ObjectOrReference, Schema, Spec};
+use openapi_schema::{Generator, Int128Policy, OpenapiSchema};
+use serde_json::json;
+
+fn schema<T: OpenapiSchema + ?Sized>() -> Schema {
+    match T::generate_schema(&mut Spec::default()) {
+        ObjectOrReference::Object(schema) => schema,
+        _ => panic!("unexpected reference"),
+    }
+}
+
+/// Schema of `T` with 128-bit integers as strings.
+fn string_schema<T: OpenapiSchema + ?Sized>() -> Schema {
+    let mut generator = Generator::new().int128_policy(Int128Policy::String);
+    match generator.generate::<T>() {
+        ObjectOrReference::Object(schema) => schema,
+        _ => panic!("unexpected reference"),
+    }
+}
+
+fn integer(format: &str, minimum: serde_json::Value, maximum: serde_json::Value) -> Schema {
+    Schema {
+        schema_type: Some("integer".into()),
+        format: Some(format.into()),
+        minimum: Some(minimum),
+        maximum: Some(maximum),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_integers() {
+    assert_eq!(schema::<i8>(), integer("int32", json!(-128), json!(127)));
+    assert_eq!(schema::<u8>(), integer("int32", json!(0), json!(255)));
+    assert_eq!(
+        schema::<i16>(),
+        integer("int32", json!(-32768), json!(32767))
+    );
+    assert_eq!(schema::<u16>(), integer("int32", json!(0), json!(65535)));
+    assert_eq!(
+        schema::<i32>(),
+        integer("int32", json!(i32::MIN), json!(i32::MAX))
+    );
+    assert_eq!(schema::<u32>(), integer("int64", json!(0), json!(u32::MAX)));
+    assert_eq!(
+        schema::<i64>(),
+        integer("int64", json!(i64::MIN), json!(i64::MAX))
+    );
+    assert_eq!(schema::<u64>(), integer("int64", json!(0), json!(u64::MAX)));
+    assert_eq!(schema::<usize>().minimum, Some(json!(0)));
+    assert_eq!(schema::<usize>().maximum, Some(json!(usize::MAX)));
+    assert_eq!(schema::<isize>().minimum, Some(json!(isize::MIN)));
+}
+
+#[test]
+fn test_int128_policy() {
+    assert_eq!(
+        schema::<i128>(),
+        Schema {
+            schema_type: Some("integer".into()),
+            ..Default::default()
+        }
+    );
+    assert_eq!(schema::<u128>().minimum, Some(json!(0)));
+
+    assert_eq!(
+        string_schema::<i128>(),
+        Schema {
+            schema_type: Some("string".into()),
+            pattern: Some("^-?[0-9]+$".into()),
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        string_schema::<u128>(),
+        Schema {
+            schema_type: Some("string".into()),
+            pattern: Some("^[0-9]+$".into()),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_floats() {
+    assert_eq!(schema::<f32>().format, Some("float".into()));
+    assert_eq!(schema::<f64>().format, Some("double".into()));
+    assert_eq!(schema::<f64>().schema_type, Some("number".into()));
+}
+
+#[test]
+fn test_strings() {
+    let string = Schema {
+        schema_type: Some("string".into()),
+        ..Default::default()
+    };
+    assert_eq!(schema::<String>(), string);
+    assert_eq!(schema::<str>(), string);
+    assert_eq!(
+        schema::<char>(),
+        Schema {
+            schema_type: Some("string".into()),
+            min_length: Some(1),
+            max_length: Some(1),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_bool_and_unit() {
+    assert_eq!(schema::<bool>().schema_type, Some("boolean".into()));
+    assert_eq!(serde_json::to_value(()).unwrap(), json!(null));
+    assert_eq!(
+        schema::<()>(),
+        Schema {
+            schema_type: Some("object".into()),
+            nullable: Some(true),
+            not: Some(Box::new(Schema {
+                schema_type: Some("object".into()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_non_zero() {
+    use std::num::{NonZeroI32, NonZeroU128, NonZeroU64, NonZeroU8};
+
+    let u8_schema = schema::<NonZeroU8>();
+    assert_eq!(u8_schema.minimum, Some(json!(1)));
+    assert_eq!(u8_schema.maximum, Some(json!(255)));
+
+    let u64_schema = schema::<NonZeroU64>();
+    assert_eq!(u64_schema.format, Some("int64".into()));
+    assert_eq!(u64_schema.minimum, Some(json!(1)));
+
+    let i32_schema = schema::<NonZeroI32>();
+    assert_eq!(i32_schema.minimum, Some(json!(i32::MIN)));
+    assert_eq!(
+        i32_schema.not,
+        Some(Box::new(Schema {
+            minimum: Some(json!(0)),
+            maximum: Some(json!(0)),
+            ..Default::default()
+        }))
+    );
+
+    let u128_schema = string_schema::<NonZeroU128>();
+    assert_eq!(u128_schema.schema_type, Some("string".into()));
+    assert_eq!(u128_schema.pattern, Some("^[1-9][0-9]*$".into()));
+}
+
+#[test]
+fn test_wrapping_and_saturating() {
+    use std::num::{Saturating, Wrapping};
+
+    assert_eq!(schema::<Wrapping<u8>>(), schema::<u8>());
+    assert_eq!(schema::<Saturating<i16>>(), schema::<i16>());
+}
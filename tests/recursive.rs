@@ -0,0 +1,96 @@
+use openapi::v3_0::{ObjectOrReference, Spec};
+use openapi_schema::OpenapiSchema;
+use serde::Serialize;
+
+mod common;
+
+use common::component;
+
+#[derive(OpenapiSchema, Serialize)]
+struct TreeNode {
+    value: u64,
+    children: Vec<TreeNode>,
+}
+
+#[derive(OpenapiSchema, Serialize)]
+struct Department {
+    name: String,
+    employees: Vec<Employee>,
+}
+
+#[derive(OpenapiSchema, Serialize)]
+struct Employee {
+    name: String,
+    departments: Option<Vec<Department>>,
+}
+
+#[derive(OpenapiSchema, Serialize)]
+#[allow(dead_code)]
+enum Expr {
+    Literal(u64),
+    Sum(Vec<Expr>),
+}
+
+#[test]
+fn test_self_recursive() {
+    let mut spec = Spec::default();
+    let reference = TreeNode::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    assert_eq!(
+        reference,
+        ObjectOrReference::Ref {
+            ref_path: "#/components/schemas/TreeNode".into()
+        }
+    );
+
+    let tree_node = component(&spec, "TreeNode");
+    let children = &tree_node.properties.as_ref().unwrap()["children"];
+    assert_eq!(children.schema_type, Some("array".into()));
+    assert_eq!(
+        children.items.as_ref().unwrap().ref_path,
+        Some("#/components/schemas/TreeNode".into())
+    );
+}
+
+#[test]
+fn test_mutually_recursive() {
+    let mut spec = Spec::default();
+    Department::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let department = component(&spec, "Department");
+    let employees = &department.properties.as_ref().unwrap()["employees"];
+    assert_eq!(
+        employees.items.as_ref().unwrap().ref_path,
+        Some("#/components/schemas/Employee".into())
+    );
+
+    let employee = component(&spec, "Employee");
+    let departments = &employee.properties.as_ref().unwrap()["departments"];
+    assert_eq!(
+        departments.items.as_ref().unwrap().ref_path,
+        Some("#/components/schemas/Department".into())
+    );
+    assert_eq!(employee.required, Some(vec!["name".into()]));
+}
+
+#[test]
+fn test_recursive_enum() {
+    let mut spec = Spec::default();
+    Expr::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let expr = component(&spec, "Expr");
+    let variants = expr.one_of.as_ref().unwrap();
+    assert_eq!(variants.len(), 2);
+
+    let sum = match &variants[1] {
+        ObjectOrReference::Object(sum) => &sum.properties.as_ref().unwrap()["Sum"],
+        _ => panic!("unexpected reference"),
+    };
+    assert_eq!(
+        sum.items.as_ref().unwrap().ref_path,
+        Some("#/components/schemas/Expr".into())
+    );
+}
@@ -0,0 +1,80 @@
+use openapi::v3_0::{ObjectOrReference, Schema, Spec};
+use openapi_schema::OpenapiSchema;
+use serde::Serialize;
+
+fn component<T: OpenapiSchema>(name: &str) -> Schema {
+    let mut spec = Spec::default();
+    T::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.unwrap().schemas.unwrap();
+    match schemas.get(name) {
+        Some(ObjectOrReference::Object(schema)) => schema.clone(),
+        _ => panic!("unexpected reference"),
+    }
+}
+
+macro_rules! test_rename_all {
+    ($test:ident, $rule:expr) => {
+        #[test]
+        fn $test() {
+            #[derive(OpenapiSchema, Serialize, Default)]
+            #[serde(rename_all = $rule)]
+            struct Fields {
+                id: u64,
+                user_name: String,
+                photo_urls: Vec<String>,
+                r#type: String,
+                #[serde(rename = "explicitName")]
+                renamed: bool,
+            }
+
+            #[derive(OpenapiSchema, Serialize)]
+            #[serde(rename_all = $rule)]
+            #[allow(dead_code)]
+            enum Variants {
+                Available,
+                OutOfStock,
+                #[serde(rename = "explicit_name")]
+                Renamed,
+            }
+
+            let value = serde_json::to_value(&Fields::default()).unwrap();
+            let mut expected: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+            expected.sort();
+
+            let fields = component::<Fields>("Fields");
+            let mut properties: Vec<String> = fields.properties.unwrap().keys().cloned().collect();
+            properties.sort();
+            assert_eq!(properties, expected);
+
+            let mut required = fields.required.unwrap();
+            required.sort();
+            assert_eq!(required, expected);
+
+            let expected: Vec<String> =
+                [Variants::Available, Variants::OutOfStock, Variants::Renamed]
+                    .iter()
+                    .map(|v| {
+                        serde_json::to_value(v)
+                            .unwrap()
+                            .as_str()
+                            .unwrap()
+                            .to_owned()
+                    })
+                    .collect();
+
+            let variants = component::<Variants>("Variants");
+            assert_eq!(variants.enum_values, Some(expected));
+        }
+    };
+}
+
+test_rename_all!(test_lowercase, "lowercase");
+test_rename_all!(test_uppercase, "UPPERCASE");
+test_rename_all!(test_pascal_case, "PascalCase");
+test_rename_all!(test_camel_case, "camelCase");
+test_rename_all!(test_snake_case, "snake_case");
+test_rename_all!(test_screaming_snake_case, "SCREAMING_SNAKE_CASE");
+test_rename_all!(test_kebab_case, "kebab-case");
+test_rename_all!(test_screaming_kebab_case, "SCREAMING-KEBAB-CASE");
@@ -0,0 +1,10 @@
+use openapi_schema::OpenapiSchema;
+
+#[derive(OpenapiSchema)]
+#[openapi(inline)]
+enum Status {
+    Active,
+    Deleted,
+}
+
+fn main() {}
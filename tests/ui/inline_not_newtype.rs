@@ -0,0 +1,9 @@
+use openapi_schema::OpenapiSchema;
+
+#[derive(OpenapiSchema)]
+#[openapi(inline)]
+struct NotNewtype {
+    a: u32,
+}
+
+fn main() {}
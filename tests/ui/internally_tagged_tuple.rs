@@ -0,0 +1,10 @@
+use openapi_schema::OpenapiSchema;
+
+#[derive(OpenapiSchema)]
+#[serde(tag = "type")]
+enum Shape {
+    Circle { radius: f64 },
+    Point(f64, f64),
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use openapi_schema::OpenapiSchema;
+
+#[derive(OpenapiSchema)]
+struct User {
+    #[serde(default = "not a path")]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use openapi_schema::OpenapiSchema;
+
+#[derive(OpenapiSchema)]
+struct User {
+    #[openapi(max_length = "64")]
+    name: String,
+}
+
+fn main() {}
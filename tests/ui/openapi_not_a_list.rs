@@ -0,0 +1,9 @@
+use openapi_schema::OpenapiSchema;
+
+#[derive(OpenapiSchema)]
+#[openapi = "User"]
+struct User {
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use openapi_schema::OpenapiSchema;
+
+#[derive(OpenapiSchema)]
+#[openapi(inline, rename = "UserId")]
+struct Id(u64);
+
+fn main() {}
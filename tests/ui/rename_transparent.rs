@@ -0,0 +1,8 @@
+use openapi_schema::OpenapiSchema;
+
+#[derive(OpenapiSchema)]
+#[serde(transparent)]
+#[openapi(rename = "UserId")]
+struct Id(u64);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use openapi_schema::OpenapiSchema;
+
+#[derive(OpenapiSchema)]
+#[serde(transparent)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+fn main() {}
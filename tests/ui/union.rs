@@ -0,0 +1,9 @@
+use openapi_schema::OpenapiSchema;
+
+#[derive(OpenapiSchema)]
+union Number {
+    int: i64,
+    float: f64,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use openapi_schema::OpenapiSchema;
+
+#[derive(OpenapiSchema)]
+struct User {
+    #[openapi(min_lenght = 1)]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use openapi_schema::OpenapiSchema;
+
+#[derive(OpenapiSchema)]
+#[serde(rename_all = "Title Case")]
+struct User {
+    first_name: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use openapi_schema::OpenapiSchema;
+
+#[derive(OpenapiSchema)]
+enum Status {
+    Active,
+    #[openapi(deprecated)]
+    Deleted,
+}
+
+fn main() {}
@@ -0,0 +1,94 @@
+#![cfg(feature = "validator")]
+
+use lazy_static::lazy_static;
+use openapi::v3_0::{ObjectOrReference, Spec};
+use openapi_schema::OpenapiSchema;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::json;
+use validator::Validate;
+
+lazy_static! {
+    static ref USERNAME: Regex = Regex::new("^[a-z_]+$").unwrap();
+}
+
+#[derive(OpenapiSchema, Serialize, Validate)]
+struct Signup {
+    #[validate(length(min = 1, max = 64), regex = "USERNAME")]
+    username: String,
+    #[validate(email)]
+    email: String,
+    #[validate(url)]
+    homepage: Option<String>,
+    #[validate(range(min = 18, max = 150))]
+    age: u32,
+    #[validate(range(min = 0.5))]
+    #[openapi(maximum = 10)]
+    rating: f64,
+    #[validate(length(equal = 3))]
+    tags: Vec<String>,
+    #[validate(length(min = 1))]
+    #[openapi(min_length = 2)]
+    nickname: String,
+    #[validate(range(min = -10, max = 10), required)]
+    offset: Option<i32>,
+    #[validate(range(min = -0.5))]
+    balance: f64,
+}
+
+#[test]
+fn test_validator_constraints() {
+    let mut spec = Spec::default();
+    Signup::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let schemas = spec.components.as_ref().unwrap().schemas.as_ref().unwrap();
+    let signup = match schemas.get("Signup") {
+        Some(ObjectOrReference::Object(signup)) => signup,
+        _ => panic!("missing Signup"),
+    };
+    let properties = signup.properties.as_ref().unwrap();
+
+    let username = &properties["username"];
+    assert_eq!(username.min_length, Some(1));
+    assert_eq!(username.max_length, Some(64));
+    assert_eq!(username.pattern, Some("^[a-z_]+$".into()));
+
+    assert_eq!(properties["email"].format, Some("email".into()));
+    assert_eq!(properties["homepage"].format, Some("uri".into()));
+
+    let age = &properties["age"];
+    assert_eq!(age.minimum, Some(json!(18)));
+    assert_eq!(age.maximum, Some(json!(150)));
+
+    // explicit openapi attributes take precedence
+    let rating = &properties["rating"];
+    assert_eq!(rating.minimum, Some(json!(0.5)));
+    assert_eq!(rating.maximum, Some(json!(10)));
+    assert_eq!(properties["nickname"].min_length, Some(2));
+
+    // negative numbers do not drop the attribute
+    let offset = &properties["offset"];
+    assert_eq!(offset.minimum, Some(json!(-10)));
+    assert_eq!(offset.maximum, Some(json!(10)));
+    let balance = &properties["balance"];
+    assert_eq!(balance.minimum, Some(json!(-0.5)));
+
+    let tags = &properties["tags"];
+    assert_eq!(tags.min_items, Some(3));
+    assert_eq!(tags.max_items, Some(3));
+    assert_eq!(tags.min_length, None);
+
+    let signup = Signup {
+        username: "jane_doe".into(),
+        email: "jane@example.com".into(),
+        homepage: Some("https://example.com".into()),
+        age: 42,
+        rating: 3.0,
+        tags: vec!["a".into(), "b".into(), "c".into()],
+        nickname: "jd".into(),
+        offset: Some(-3),
+        balance: 0.0,
+    };
+    assert!(signup.validate().is_ok());
+}
@@ -0,0 +1,85 @@
+use std::cell::{Cell, RefCell};
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, RwLock};
+
+use openapi::v3_0::{ObjectOrReference, Schema, Spec};
+use openapi_schema::OpenapiSchema;
+use serde::Serialize;
+
+mod common;
+
+use common::component;
+
+#[derive(OpenapiSchema, Serialize)]
+struct User {
+    name: String,
+}
+
+#[derive(OpenapiSchema)]
+#[allow(dead_code)]
+struct Session {
+    user: Arc<User>,
+    owner: Box<User>,
+    shared: Rc<User>,
+    hits: Cell<u8>,
+    label: RefCell<String>,
+    tags: Mutex<Vec<String>>,
+    title: RwLock<Box<str>>,
+    marker: PhantomData<User>,
+}
+
+#[derive(OpenapiSchema, Serialize)]
+struct List {
+    value: u64,
+    next: Option<Box<List>>,
+}
+
+#[test]
+fn test_forwarding() {
+    let mut spec = Spec::default();
+    Session::generate_schema(&mut spec);
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+
+    let session = component(&spec, "Session");
+    let properties = session.properties.as_ref().unwrap();
+    for name in &["user", "owner", "shared"] {
+        assert_eq!(
+            properties[*name].ref_path,
+            Some("#/components/schemas/User".into())
+        );
+    }
+    assert_eq!(properties["hits"].schema_type, Some("integer".into()));
+    assert_eq!(properties["hits"].format, Some("int32".into()));
+    assert_eq!(properties["label"].schema_type, Some("string".into()));
+    assert_eq!(properties["tags"].schema_type, Some("array".into()));
+    assert_eq!(properties["title"].schema_type, Some("string".into()));
+    assert_eq!(
+        properties["marker"],
+        Schema {
+            schema_type: Some("object".into()),
+            nullable: Some(true),
+            not: Some(Box::new(Schema {
+                schema_type: Some("object".into()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+    );
+    assert_eq!(Arc::<User>::schema_name(), "User");
+}
+
+#[test]
+fn test_recursion_through_box() {
+    let mut spec = Spec::default();
+    let list = List::generate_schema(&mut spec);
+    assert_eq!(
+        list,
+        ObjectOrReference::Ref {
+            ref_path: "#/components/schemas/List".into()
+        }
+    );
+
+    let next = &component(&spec, "List").properties.as_ref().unwrap()["next"];
+    assert_eq!(next.ref_path, Some("#/components/schemas/List".into()));
+}